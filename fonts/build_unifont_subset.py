@@ -0,0 +1,239 @@
+"""Converts the Greek and Runic glyphs of GNU Unifont into a TrueType font.
+
+Every pixel row of a glyph becomes a few rectangles, so the font looks exactly like Unifont.
+
+Usage: python3 build_unifont_subset.py path/to/unifont.hex UnifontSubset.ttf
+"""
+
+import struct
+import sys
+
+# Greek and Coptic, Runic.
+RANGES = [(0x0370, 0x03FF), (0x16A0, 0x16FF)]
+UNITS_PER_PIXEL = 64
+UNITS_PER_EM = 16 * UNITS_PER_PIXEL
+ASCENT = 14 * UNITS_PER_PIXEL
+DESCENT = 2 * UNITS_PER_PIXEL
+
+FAMILY = "Unifont Subset"
+POSTSCRIPT_NAME = "UnifontSubset-Regular"
+VERSION = "Version 15.1.05"
+COPYRIGHT = "Copyright (C) 1998-2023 Roman Czyborra, Paul Hardy, Qianqian Fang, Andrew Miller, Johnnie Weaver, David Corbett, Nils Moskopp, Rebecca Bettencourt, et al."
+LICENSE = "Dual licensed under the SIL Open Font License 1.1 and the GNU GPL 2+ with the GNU font embedding exception."
+LICENSE_URL = "https://openfontlicense.org"
+
+
+def read_hex(path):
+    glyphs = {}
+    with open(path) as file:
+        for line in file:
+            code, bitmap = line.strip().split(":")
+            code = int(code, 16)
+            if any(first <= code <= last for first, last in RANGES):
+                glyphs[code] = bitmap
+    return glyphs
+
+
+def rectangles(bitmap):
+    """Returns the width in pixels and a rectangle (x0, x1, row) for every horizontal run of pixels."""
+    width = len(bitmap) // 4  # 32 hex digits for 8 pixels wide, 64 for 16 pixels wide.
+    row_digits = width // 4
+    result = []
+    for row in range(16):
+        bits = int(bitmap[row * row_digits:(row + 1) * row_digits], 16)
+        x = 0
+        while x < width:
+            if bits & (1 << (width - 1 - x)):
+                start = x
+                while x < width and bits & (1 << (width - 1 - x)):
+                    x += 1
+                result.append((start, x, row))
+            else:
+                x += 1
+    return width, result
+
+
+def glyph_data(runs):
+    if not runs:
+        return b"", (0, 0, 0, 0), 0, 0
+    contours = []
+    for x0, x1, row in runs:
+        top = ASCENT - row * UNITS_PER_PIXEL
+        bottom = top - UNITS_PER_PIXEL
+        left, right = x0 * UNITS_PER_PIXEL, x1 * UNITS_PER_PIXEL
+        # Clockwise, as TrueType expects for filled contours.
+        contours.append([(left, bottom), (left, top), (right, top), (right, bottom)])
+    points = [point for contour in contours for point in contour]
+    xs = [x for x, _ in points]
+    ys = [y for _, y in points]
+    bounds = (min(xs), min(ys), max(xs), max(ys))
+
+    data = struct.pack(">hhhhh", len(contours), *bounds)
+    end = -1
+    for contour in contours:
+        end += len(contour)
+        data += struct.pack(">H", end)
+    data += struct.pack(">H", 0)  # No instructions.
+    data += bytes([0x01] * len(points))  # On curve, 16 bit deltas.
+    previous = 0
+    for x in xs:
+        data += struct.pack(">h", x - previous)
+        previous = x
+    previous = 0
+    for y in ys:
+        data += struct.pack(">h", y - previous)
+        previous = y
+    while len(data) % 4:
+        data += b"\0"
+    return data, bounds, len(points), len(contours)
+
+
+def cmap_table(codes):
+    """A format 4 subtable. `codes` maps every character to its glyph index."""
+    segments = []
+    for code in sorted(codes):
+        if segments and segments[-1][1] == code - 1 and codes[code] == codes[segments[-1][1]] + 1:
+            segments[-1][1] = code
+        else:
+            segments.append([code, code])
+    segments.append([0xFFFF, 0xFFFF])
+
+    count = len(segments)
+    search_range = 2 * 2 ** (count.bit_length() - 1)
+    subtable = struct.pack(
+        ">HHHHH",
+        count * 2,
+        search_range,
+        count.bit_length() - 1,
+        count * 2 - search_range,
+        0,
+    )
+    ends = b"".join(struct.pack(">H", end) for _, end in segments)
+    starts = b"".join(struct.pack(">H", start) for start, _ in segments)
+    deltas = b"".join(
+        struct.pack(">h", 1 if start == 0xFFFF else ((codes[start] - start + 0x8000) % 0x10000) - 0x8000)
+        for start, _ in segments
+    )
+    offsets = b"\0\0" * count
+    body = subtable[:8] + ends + b"\0\0" + starts + deltas + offsets
+    header = struct.pack(">HHH", 4, 6 + len(body), 0)
+    subtable = header + body
+    return struct.pack(">HH", 0, 2) + struct.pack(">HHI", 0, 3, 20) + struct.pack(">HHI", 3, 1, 20) + subtable
+
+
+def name_table():
+    names = {
+        0: COPYRIGHT,
+        1: FAMILY,
+        2: "Regular",
+        3: POSTSCRIPT_NAME,
+        4: FAMILY,
+        5: VERSION,
+        6: POSTSCRIPT_NAME,
+        13: LICENSE,
+        14: LICENSE_URL,
+    }
+    records = b""
+    strings = b""
+    for name_id, value in names.items():
+        encoded = value.encode("utf-16-be")
+        records += struct.pack(">HHHHHH", 3, 1, 0x409, name_id, len(encoded), len(strings))
+        strings += encoded
+    return struct.pack(">HHH", 0, len(names), 6 + len(records)) + records + strings
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(">%dI" % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def build(glyphs):
+    codes = sorted(glyphs)
+    # Glyph 0 is .notdef, glyph 1 the space.
+    outlines = [(b"", (0, 0, 0, 0), 0, 0), (b"", (0, 0, 0, 0), 0, 0)]
+    widths = [8, 8]
+    for code in codes:
+        width, runs = rectangles(glyphs[code])
+        outlines.append(glyph_data(runs))
+        widths.append(width)
+    char_to_glyph = {0x20: 1}
+    char_to_glyph.update({code: index + 2 for index, code in enumerate(codes)})
+
+    glyf = b""
+    loca = b""
+    for data, *_ in outlines:
+        loca += struct.pack(">I", len(glyf))
+        glyf += data
+    loca += struct.pack(">I", len(glyf))
+
+    bounds = [bounds for data, bounds, *_ in outlines if data]
+    x_min = min(b[0] for b in bounds)
+    y_min = min(b[1] for b in bounds)
+    x_max = max(b[2] for b in bounds)
+    y_max = max(b[3] for b in bounds)
+    advance_max = max(widths) * UNITS_PER_PIXEL
+
+    hmtx = b"".join(
+        struct.pack(">Hh", width * UNITS_PER_PIXEL, outline[1][0])
+        for width, outline in zip(widths, outlines)
+    )
+    head = struct.pack(
+        ">HHIIIHHqqhhhhHHhh",
+        1, 0, 0x000F_0000, 0, 0x5F0F3CF5, 0x000B, UNITS_PER_EM,
+        0, 0, x_min, y_min, x_max, y_max, 0, 8, 2, 1,
+    ) + struct.pack(">h", 0)
+    hhea = struct.pack(
+        ">HHhhhHhhhhhhhhhhhH",
+        1, 0, ASCENT, -DESCENT, 0, advance_max, 0, 0, x_max, 1, 0, 0, 0, 0, 0, 0, 0, len(outlines),
+    )
+    maxp = struct.pack(
+        ">IHHHHHHHHHHHHHH",
+        0x00010000, len(outlines),
+        max(outline[2] for outline in outlines), max(outline[3] for outline in outlines),
+        0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0,
+    )
+    os2 = struct.pack(
+        ">HhHHHhhhhhhhhhhh10sIIII4sHHHhhhHHIIhhHHH",
+        4, 8 * UNITS_PER_PIXEL, 400, 5, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        b"\0" * 10, 0, 0, 1 << (79 - 64), 0, b"NONE", 0x00C0,
+        0x20, max(codes), ASCENT, -DESCENT, 0, ASCENT, DESCENT, 0, 0,
+        8 * UNITS_PER_PIXEL, 10 * UNITS_PER_PIXEL, 0, 0x20, 0,
+    )
+    post = struct.pack(">IIhhIIIII", 0x00030000, 0, -UNITS_PER_PIXEL, UNITS_PER_PIXEL, 0, 0, 0, 0, 0)
+
+    tables = {
+        b"OS/2": os2,
+        b"cmap": cmap_table(char_to_glyph),
+        b"glyf": glyf,
+        b"head": head,
+        b"hhea": hhea,
+        b"hmtx": hmtx,
+        b"loca": loca,
+        b"maxp": maxp,
+        b"name": name_table(),
+        b"post": post,
+    }
+
+    count = len(tables)
+    search_range = 16 * 2 ** (count.bit_length() - 1)
+    font = struct.pack(">IHHHH", 0x00010000, count, search_range, count.bit_length() - 1, count * 16 - search_range)
+    offset = 12 + 16 * count
+    directory = b""
+    data = b""
+    head_offset = 0
+    for tag in sorted(tables):
+        table = tables[tag]
+        if tag == b"head":
+            head_offset = offset + len(data)
+        directory += struct.pack(">4sIII", tag, checksum(table), offset + len(data), len(table))
+        data += table + b"\0" * (-len(table) % 4)
+    font += directory + data
+
+    adjustment = (0xB1B0AFBA - checksum(font)) & 0xFFFFFFFF
+    return font[: head_offset + 8] + struct.pack(">I", adjustment) + font[head_offset + 12:]
+
+
+if __name__ == "__main__":
+    with open(sys.argv[2], "wb") as output:
+        output.write(build(read_hex(sys.argv[1])))
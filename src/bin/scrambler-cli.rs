@@ -0,0 +1,381 @@
+use chrono::NaiveDate;
+use clap::Parser;
+use clap::Subcommand;
+use clap::ValueEnum;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::Read;
+use std::process;
+
+use ::scrambler::scrambler::dictionary::DictionaryQuery;
+use ::scrambler::scrambler::dictionary::DictionarySort;
+use ::scrambler::scrambler::dictionary::SearchDirection;
+use ::scrambler::scrambler::Translation;
+use scrambler::scrambler;
+
+/// Command-line access to the scrambler. Uses the same `scrambler_data` folder as the UI.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Print the results as JSON instead of plain text.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Translate English text. New translations are saved, unless --dry-run is given.
+    /// Without words, every line of stdin is translated.
+    Translate {
+        words: Vec<String>,
+        /// Don't save newly generated translations.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Translate scrambled text back to English. Unknown words are shown between brackets.
+    /// Without words, every line of stdin is translated.
+    Reverse { words: Vec<String> },
+    /// Search the known translations.
+    Lookup {
+        /// Text the English or scrambled word must contain. Lists everything if omitted.
+        #[arg(default_value = "")]
+        search: String,
+        #[arg(long, value_enum, default_value_t = Direction::Both)]
+        direction: Direction,
+        #[arg(long, value_enum, default_value_t = Sort::English)]
+        sort: Sort,
+        #[arg(long)]
+        descending: bool,
+        /// Starts at 1.
+        #[arg(long, default_value_t = 1)]
+        page: usize,
+        #[arg(long, default_value_t = 50)]
+        page_size: usize,
+    },
+    /// Add letters to the end of the alphabet.
+    AddGlyph {
+        #[arg(required = true)]
+        letters: Vec<String>,
+    },
+    /// Add words to the block list, so they are never generated.
+    Block {
+        #[arg(required = true)]
+        words: Vec<String>,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Write all known translations as JSON, to a file or to stdout.
+    Export { file: Option<String> },
+    /// Add translations from a file or from stdin, in the format written by export.
+    Import {
+        file: Option<String>,
+        /// Replace the translation of words that already have a different one.
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Undo the most recent change to the translations, the alphabet or the block list.
+    Undo,
+    /// Redo the most recently undone change.
+    Redo,
+    /// Mark words as revealed to a party of players.
+    Reveal {
+        party: String,
+        #[arg(required = true)]
+        words: Vec<String>,
+        /// The date of the session, like 2024-03-01. Defaults to today.
+        #[arg(long)]
+        session: Option<NaiveDate>,
+    },
+    /// Forget that words were revealed to a party.
+    Hide {
+        party: String,
+        #[arg(required = true)]
+        words: Vec<String>,
+    },
+    /// List the words a party knows and the words it doesn't know yet.
+    Vocabulary {
+        party: String,
+        /// Only list the words the party knows.
+        #[arg(long, conflicts_with = "unknown")]
+        known: bool,
+        /// Only list the words the party doesn't know yet.
+        #[arg(long)]
+        unknown: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Direction {
+    Both,
+    English,
+    Scrambled,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Sort {
+    English,
+    Scrambled,
+    TimeAdded,
+}
+
+fn main() {
+    env_logger::init();
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("{error}");
+        process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Translate { words, dry_run } => {
+            let mut new_words = HashMap::new();
+            for line in input_lines(words)? {
+                translate_line(&line, dry_run, cli.json, &mut new_words)?;
+            }
+        }
+        Command::Reverse { words } => {
+            for line in input_lines(words)? {
+                reverse_line(&line, cli.json);
+            }
+        }
+        Command::Lookup {
+            search,
+            direction,
+            sort,
+            descending,
+            page,
+            page_size,
+        } => {
+            let query = DictionaryQuery {
+                search,
+                direction: match direction {
+                    Direction::Both => SearchDirection::Both,
+                    Direction::English => SearchDirection::English,
+                    Direction::Scrambled => SearchDirection::Scrambled,
+                },
+                sort: match sort {
+                    Sort::English => DictionarySort::English,
+                    Sort::Scrambled => DictionarySort::Scrambled,
+                    Sort::TimeAdded => DictionarySort::TimeAdded,
+                },
+                descending,
+                page: page.saturating_sub(1),
+                page_size,
+            };
+            let result = scrambler::search_dictionary(&query);
+            if cli.json {
+                let entries = result
+                    .entries
+                    .iter()
+                    .map(|(word, translation)| json!({ "word": word, "translation": translation }))
+                    .collect::<Vec<_>>();
+                println!(
+                    "{}",
+                    json!({
+                        "entries": entries,
+                        "total_matches": result.total_matches,
+                        "page": result.page + 1,
+                        "page_count": result.page_count,
+                    })
+                );
+            } else {
+                for (word, translation) in result.entries {
+                    println!("{word}\t{}", translation.translation);
+                }
+                eprintln!(
+                    "Page {} of {} ({} words)",
+                    result.page + 1,
+                    result.page_count,
+                    result.total_matches
+                );
+            }
+        }
+        Command::AddGlyph { letters } => {
+            for letter in letters {
+                scrambler::add_to_alphabet(&letter)?;
+            }
+        }
+        Command::Block { words, reason } => {
+            for word in words {
+                scrambler::add_to_block_list(&word, reason.as_deref())?;
+            }
+        }
+        Command::Export { file } => {
+            // Sorted, so that exporting the same dictionary twice gives the same file.
+            let translations: BTreeMap<_, _> = scrambler::storage::load_translated_words()
+                .into_iter()
+                .collect();
+            let json = serde_json::to_string_pretty(&translations)?;
+            match file {
+                Some(file) => fs::write(file, json)?,
+                None => println!("{json}"),
+            }
+        }
+        Command::Import { file, overwrite } => {
+            let json = match file {
+                Some(file) => fs::read_to_string(file)?,
+                None => {
+                    let mut json = String::new();
+                    io::stdin().read_to_string(&mut json)?;
+                    json
+                }
+            };
+            let translations: HashMap<String, Translation> = serde_json::from_str(&json)?;
+            let changed = scrambler::import_translations(translations, overwrite)?;
+            if cli.json {
+                println!("{}", json!({ "changed": changed }));
+            } else {
+                println!("Imported {changed} translations.");
+            }
+        }
+        Command::Undo => match scrambler::undo()? {
+            Some(description) => println!("Undid: {description}"),
+            None => println!("Nothing to undo."),
+        },
+        Command::Redo => match scrambler::redo()? {
+            Some(description) => println!("Redid: {description}"),
+            None => println!("Nothing to redo."),
+        },
+        Command::Reveal {
+            party,
+            words,
+            session,
+        } => {
+            let session = session.unwrap_or_else(|| chrono::Local::now().date_naive());
+            let changed = scrambler::reveal_words(&party, &words, session)?;
+            if cli.json {
+                println!("{}", json!({ "changed": changed }));
+            } else {
+                println!("Revealed {changed} words to {}.", party.trim());
+            }
+        }
+        Command::Hide { party, words } => {
+            let changed = scrambler::hide_words(&party, &words)?;
+            if cli.json {
+                println!("{}", json!({ "changed": changed }));
+            } else {
+                println!("Hid {changed} words from {}.", party.trim());
+            }
+        }
+        Command::Vocabulary {
+            party,
+            known,
+            unknown,
+        } => {
+            let entries = scrambler::party_vocabulary(&party)
+                .into_iter()
+                .filter(|entry| match entry.revealed {
+                    Some(_) => !unknown,
+                    None => !known,
+                })
+                .collect::<Vec<_>>();
+            if cli.json {
+                println!("{}", json!(entries));
+            } else {
+                for entry in entries {
+                    match &entry.revealed {
+                        Some(revealed) if entry.is_outdated() => println!(
+                            "{}\t{}\tknown since {}, as {}",
+                            entry.word, entry.translation, revealed.session, revealed.translation
+                        ),
+                        Some(revealed) => println!(
+                            "{}\t{}\tknown since {}",
+                            entry.word, entry.translation, revealed.session
+                        ),
+                        None => println!("{}\t{}\tunknown", entry.word, entry.translation),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The words given on the command line as a single line, or else all lines of stdin.
+fn input_lines(words: Vec<String>) -> Result<Vec<String>, Box<dyn Error>> {
+    if !words.is_empty() {
+        return Ok(vec![words.join(" ")]);
+    }
+    Ok(io::stdin().lock().lines().collect::<Result<_, _>>()?)
+}
+
+/// `new_words` remembers the words that got a new translation, so a word that appears again gets
+/// the same translation, even if nothing is saved in a dry run.
+fn translate_line(
+    line: &str,
+    dry_run: bool,
+    json: bool,
+    new_words: &mut HashMap<String, Translation>,
+) -> Result<(), Box<dyn Error>> {
+    let mut results = vec![];
+    for word in line.split_whitespace() {
+        let word = scrambler::strip_punctuation(word);
+        if word.is_empty() {
+            continue;
+        }
+        if let Some(translation) = new_words.get(&word) {
+            results.push((word, translation.translation.clone(), false));
+            continue;
+        }
+        let is_new = !scrambler::is_word_known(&word);
+        let translation = scrambler::translate_word(&word)?;
+        if is_new {
+            if !dry_run {
+                scrambler::save_translation(&word, translation.clone(), false)?;
+            }
+            new_words.insert(word.clone(), translation.clone());
+        }
+        results.push((word, translation.translation, is_new));
+    }
+
+    if json {
+        let words = results
+            .iter()
+            .map(|(word, translation, is_new)| {
+                json!({ "word": word, "translation": translation, "new": is_new })
+            })
+            .collect::<Vec<_>>();
+        println!("{}", json!(words));
+    } else {
+        let translations = results
+            .iter()
+            .map(|(_, translation, _)| translation.as_str())
+            .collect::<Vec<_>>();
+        println!("{}", translations.join(" "));
+    }
+    Ok(())
+}
+
+fn reverse_line(line: &str, json: bool) {
+    let results = line
+        .split_whitespace()
+        .map(|word| (word, scrambler::reverse_translate_word(word)))
+        .collect::<Vec<_>>();
+
+    if json {
+        let words = results
+            .iter()
+            .map(|(word, original)| json!({ "translation": word, "word": original }))
+            .collect::<Vec<_>>();
+        println!("{}", json!(words));
+    } else {
+        let originals = results
+            .iter()
+            .map(|(word, original)| match original {
+                Some(original) => original.clone(),
+                None => format!("[{word}]"),
+            })
+            .collect::<Vec<_>>();
+        println!("{}", originals.join(" "));
+    }
+}
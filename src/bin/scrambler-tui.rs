@@ -0,0 +1,411 @@
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::KeyEventKind;
+use ratatui::crossterm::event::KeyModifiers;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::List;
+use ratatui::widgets::ListState;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Tabs;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use std::collections::HashMap;
+use std::io;
+
+use ::scrambler::scrambler::dictionary;
+use ::scrambler::scrambler::dictionary::DictionaryPage;
+use ::scrambler::scrambler::dictionary::DictionaryQuery;
+use ::scrambler::scrambler::dictionary::DictionarySort;
+use ::scrambler::scrambler::dictionary::SearchDirection;
+use ::scrambler::scrambler::Translation;
+use scrambler::scrambler;
+
+fn main() -> io::Result<()> {
+    // No logger is set up: log messages on stderr would mess up the screen. Errors are shown in the status line.
+    let mut terminal = ratatui::init();
+    let result = Tui::new().run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Translate,
+    Dictionary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Input,
+    Words,
+}
+
+/// A word of the sentence being translated.
+struct WordRow {
+    word: String,
+    translation: Translation,
+    /// `false` for words that already had a translation, or a suggestion that was accepted.
+    is_suggestion: bool,
+}
+
+struct Tui {
+    tab: Tab,
+    focus: Focus,
+    input: String,
+    words: Vec<WordRow>,
+    word_state: ListState,
+    dictionary: HashMap<String, Translation>,
+    dictionary_query: DictionaryQuery,
+    dictionary_page: DictionaryPage,
+    dictionary_state: ListState,
+    status: String,
+    quit: bool,
+}
+
+impl Tui {
+    fn new() -> Tui {
+        let mut tui = Tui {
+            tab: Tab::Translate,
+            focus: Focus::Input,
+            input: String::new(),
+            words: Vec::new(),
+            word_state: ListState::default(),
+            dictionary: scrambler::storage::load_translated_words(),
+            dictionary_query: DictionaryQuery::default(),
+            dictionary_page: DictionaryPage::default(),
+            dictionary_state: ListState::default(),
+            status: String::new(),
+            quit: false,
+        };
+        tui.query_dictionary();
+        tui
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        if key.code == KeyCode::Tab {
+            self.tab = match self.tab {
+                Tab::Translate => Tab::Dictionary,
+                Tab::Dictionary => Tab::Translate,
+            };
+            return;
+        }
+
+        match self.tab {
+            Tab::Translate => match self.focus {
+                Focus::Input => self.handle_input_key(key),
+                Focus::Words => self.handle_words_key(key),
+            },
+            Tab::Dictionary => self.handle_dictionary_key(key),
+        }
+    }
+
+    fn handle_input_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Enter => self.translate_input(),
+            KeyCode::Down if !self.words.is_empty() => self.focus = Focus::Words,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(character) => self.input.push(character),
+            _ => {}
+        }
+    }
+
+    fn handle_words_key(&mut self, key: KeyEvent) {
+        let selected = self.word_state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('i') => self.focus = Focus::Input,
+            KeyCode::Up | KeyCode::Char('k') => {
+                if selected == 0 {
+                    self.focus = Focus::Input;
+                } else {
+                    self.word_state.select_previous();
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.word_state.select_next(),
+            KeyCode::Char('a') => self.accept(selected),
+            KeyCode::Char('A') => {
+                for index in 0..self.words.len() {
+                    self.regenerate_if_taken(index);
+                    self.accept(index);
+                }
+            }
+            KeyCode::Char('r') => self.regenerate(selected),
+            KeyCode::Char('b') => {
+                if let Some(row) = self.words.get(selected).filter(|row| row.is_suggestion) {
+                    let reason = format!("Rejected as translation of \"{}\"", row.word);
+                    match scrambler::add_to_block_list(&row.translation.translation, Some(&reason))
+                    {
+                        Ok(()) => self.regenerate(selected),
+                        Err(error) => self.status = error.to_string(),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_dictionary_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Up => self.dictionary_state.select_previous(),
+            KeyCode::Down => self.dictionary_state.select_next(),
+            KeyCode::PageUp => {
+                self.dictionary_query.page = self.dictionary_query.page.saturating_sub(1);
+                self.query_dictionary();
+            }
+            KeyCode::PageDown => {
+                self.dictionary_query.page += 1;
+                self.query_dictionary();
+            }
+            KeyCode::F(2) => {
+                let sorts = DictionarySort::ALL;
+                let index = sorts
+                    .iter()
+                    .position(|sort| *sort == self.dictionary_query.sort)
+                    .unwrap_or(0);
+                self.dictionary_query.sort = sorts[(index + 1) % sorts.len()];
+                self.query_dictionary();
+            }
+            KeyCode::F(3) => {
+                let directions = SearchDirection::ALL;
+                let index = directions
+                    .iter()
+                    .position(|direction| *direction == self.dictionary_query.direction)
+                    .unwrap_or(0);
+                self.dictionary_query.direction = directions[(index + 1) % directions.len()];
+                self.dictionary_query.page = 0;
+                self.query_dictionary();
+            }
+            KeyCode::F(4) => {
+                self.dictionary_query.descending = !self.dictionary_query.descending;
+                self.query_dictionary();
+            }
+            KeyCode::Backspace => {
+                self.dictionary_query.search.pop();
+                self.dictionary_query.page = 0;
+                self.query_dictionary();
+            }
+            KeyCode::Char(character) => {
+                self.dictionary_query.search.push(character);
+                self.dictionary_query.page = 0;
+                self.query_dictionary();
+            }
+            _ => {}
+        }
+    }
+
+    fn translate_input(&mut self) {
+        self.words = Vec::new();
+        self.status = String::new();
+        for word in self.input.split_whitespace() {
+            let word = scrambler::strip_punctuation(word);
+            // A word that appears more than once gets a single row, so it has a single suggestion.
+            if word.is_empty() || self.words.iter().any(|row| row.word == word) {
+                continue;
+            }
+            let is_suggestion = !scrambler::is_word_known(&word);
+            match scrambler::translate_word(&word) {
+                Ok(translation) => self.words.push(WordRow {
+                    word,
+                    translation,
+                    is_suggestion,
+                }),
+                Err(error) => self.status = error.to_string(),
+            }
+        }
+
+        if !self.words.is_empty() {
+            self.focus = Focus::Words;
+            self.word_state.select(Some(0));
+        }
+    }
+
+    fn accept(&mut self, index: usize) {
+        let Some(row) = self.words.get_mut(index).filter(|row| row.is_suggestion) else {
+            return;
+        };
+        match scrambler::save_translation(&row.word, row.translation.clone(), false) {
+            Ok(()) => {
+                row.is_suggestion = false;
+                self.status = format!("Saved \"{}\".", row.word);
+            }
+            Err(error) => self.status = error.to_string(),
+        }
+        self.dictionary = scrambler::storage::load_translated_words();
+        self.query_dictionary();
+    }
+
+    fn regenerate(&mut self, index: usize) {
+        let Some(row) = self.words.get_mut(index) else {
+            return;
+        };
+        if !row.is_suggestion {
+            self.status = format!("\"{}\" already has a translation.", row.word);
+            return;
+        }
+        match scrambler::translate_word(&row.word) {
+            Ok(translation) => row.translation = translation,
+            Err(error) => self.status = error.to_string(),
+        }
+    }
+
+    /// Gives a suggestion a new translation if another word uses it already,
+    /// e.g. a suggestion with the same translation that was accepted before.
+    fn regenerate_if_taken(&mut self, index: usize) {
+        let Some(row) = self.words.get(index).filter(|row| row.is_suggestion) else {
+            return;
+        };
+        if scrambler::reverse_translate_word(&row.translation.translation)
+            .is_some_and(|other| other != row.word)
+        {
+            self.regenerate(index);
+        }
+    }
+
+    fn query_dictionary(&mut self) {
+        self.dictionary_page = dictionary::query(&self.dictionary, &self.dictionary_query);
+        self.dictionary_query.page = self.dictionary_page.page;
+        let selected = (!self.dictionary_page.entries.is_empty()).then_some(0);
+        self.dictionary_state.select(selected);
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs_area, main_area, status_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let selected_tab = match self.tab {
+            Tab::Translate => 0,
+            Tab::Dictionary => 1,
+        };
+        frame.render_widget(
+            Tabs::new(["Translate", "Dictionary"]).select(selected_tab),
+            tabs_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.status.as_str()).fg(Color::Red),
+            status_area,
+        );
+
+        let help = match (self.tab, self.focus) {
+            (Tab::Translate, Focus::Input) => {
+                "Enter: translate | Down: select words | Tab: dictionary | Esc: quit"
+            }
+            (Tab::Translate, Focus::Words) => {
+                "a: accept | A: accept all | r: regenerate | b: block and regenerate | i: edit sentence | Tab: dictionary"
+            }
+            (Tab::Dictionary, _) => {
+                "Type to search | PgUp/PgDn: page | F2: sort | F3: search direction | F4: reverse order | Tab: translate"
+            }
+        };
+        frame.render_widget(Paragraph::new(help).dim(), help_area);
+
+        match self.tab {
+            Tab::Translate => self.draw_translate(frame, main_area),
+            Tab::Dictionary => self.draw_dictionary(frame, main_area),
+        }
+    }
+
+    fn draw_translate(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let [input_area, words_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        let input_style = match self.focus {
+            Focus::Input => Style::new().fg(Color::Yellow),
+            Focus::Words => Style::new(),
+        };
+        frame.render_widget(
+            Paragraph::new(self.input.as_str())
+                .block(Block::bordered().title("What needs to be translated?"))
+                .style(input_style),
+            input_area,
+        );
+
+        let sentence = self
+            .words
+            .iter()
+            .map(|row| row.translation.translation.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let rows = self.words.iter().map(|row| {
+            let line = Line::from(format!("{} -> {}", row.word, row.translation.translation));
+            match row.is_suggestion {
+                true => line.fg(Color::Cyan),
+                false => line,
+            }
+        });
+        let list = List::new(rows)
+            .block(Block::bordered().title(Line::from(sentence)))
+            .highlight_symbol("> ")
+            .highlight_style(match self.focus {
+                Focus::Words => Style::new().reversed(),
+                Focus::Input => Style::new(),
+            });
+        frame.render_stateful_widget(list, words_area, &mut self.word_state);
+    }
+
+    fn draw_dictionary(&mut self, frame: &mut Frame, area: ratatui::layout::Rect) {
+        let [search_area, list_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        let query = &self.dictionary_query;
+        let title = format!(
+            "Search ({}, {}{})",
+            query.direction,
+            query.sort,
+            if query.descending { ", reversed" } else { "" }
+        );
+        frame.render_widget(
+            Paragraph::new(query.search.as_str())
+                .block(Block::bordered().title(title))
+                .fg(Color::Yellow),
+            search_area,
+        );
+
+        let page = &self.dictionary_page;
+        let rows = page
+            .entries
+            .iter()
+            .map(|(word, translation)| format!("{word} -> {}", translation.translation));
+        let list = List::new(rows)
+            .block(Block::bordered().title(format!(
+                "Page {} of {} ({} words)",
+                page.page + 1,
+                page.page_count,
+                page.total_matches
+            )))
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(list, list_area, &mut self.dictionary_state);
+    }
+}
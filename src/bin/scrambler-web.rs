@@ -0,0 +1,365 @@
+use clap::Parser;
+use log::error;
+use log::info;
+use serde::Deserialize;
+use serde_json::json;
+use serde_json::Value;
+use std::error::Error;
+use std::io::Cursor;
+use std::process;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Request;
+use tiny_http::Response;
+use tiny_http::Server;
+
+use ::scrambler::fonts::BUNDLED_FONTS;
+use ::scrambler::scrambler::dictionary::DictionaryQuery;
+use ::scrambler::scrambler::knowledge;
+use ::scrambler::scrambler::knowledge::RevealedStyle;
+use scrambler::scrambler;
+
+const INDEX_HTML: &str = include_str!("../../web/index.html");
+const APP_JS: &str = include_str!("../../web/app.js");
+const STYLE_CSS: &str = include_str!("../../web/style.css");
+
+/// Serves the scrambler as a web page, so the browser takes care of showing and copying the letters.
+/// Uses the same `scrambler_data` folder as the UI.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// The port to listen on. Only connections from this computer are accepted.
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+    let server = match Server::http(("127.0.0.1", cli.port)) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("Couldn't start the server on port {}: {error}", cli.port);
+            process::exit(1);
+        }
+    };
+    info!("Scrambler is running on http://localhost:{}", cli.port);
+
+    for request in server.incoming_requests() {
+        if let Err(error) = handle(request, cli.port) {
+            error!("Couldn't answer a request: {error}");
+        }
+    }
+}
+
+/// An API call that can't be answered, sent back as `{"error": message}`.
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn not_found() -> ApiError {
+        ApiError {
+            status: 404,
+            message: "Not found.".to_owned(),
+        }
+    }
+}
+
+impl From<Box<dyn Error>> for ApiError {
+    fn from(error: Box<dyn Error>) -> Self {
+        ApiError {
+            status: 400,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(error: serde_json::Error) -> Self {
+        ApiError {
+            status: 400,
+            message: format!("Invalid request: {error}"),
+        }
+    }
+}
+
+fn handle(mut request: Request, port: u16) -> Result<(), Box<dyn Error>> {
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+    let method = request.method().clone();
+
+    let check = check_request(
+        port,
+        &path,
+        header(&request, "Host"),
+        header(&request, "Origin"),
+        header(&request, "Content-Type"),
+    );
+    if let Err(error) = check {
+        let response = json_response(error.status, &json!({ "error": error.message }));
+        return Ok(request.respond(response)?);
+    }
+
+    if path.starts_with("/api/") {
+        let mut body = String::new();
+        request.as_reader().read_to_string(&mut body)?;
+        let (status, value) = match api(&method, &path, &body) {
+            Ok(value) => (200, value),
+            Err(error) => (error.status, json!({ "error": error.message })),
+        };
+        return Ok(request.respond(json_response(status, &value))?);
+    }
+
+    if method != Method::Get {
+        return Ok(request.respond(Response::empty(405))?);
+    }
+    match path.as_str() {
+        "/" | "/index.html" => {
+            respond_with(request, INDEX_HTML.as_bytes(), "text/html; charset=utf-8")
+        }
+        "/app.js" => respond_with(request, APP_JS.as_bytes(), "text/javascript; charset=utf-8"),
+        "/style.css" => respond_with(request, STYLE_CSS.as_bytes(), "text/css; charset=utf-8"),
+        _ => match BUNDLED_FONTS
+            .iter()
+            .find(|font| path == format!("/fonts/{}.ttf", font_file_name(font.family)))
+        {
+            Some(font) => respond_with(request, font.data, "font/ttf"),
+            None => Ok(request.respond(Response::from_string("Not found.").with_status_code(404))?),
+        },
+    }
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+/// Only the page served by this server may use it. Other websites open in the browser can't,
+/// even though they can send requests to localhost:
+/// - The `Host` must be this server, so a website that points its own domain to 127.0.0.1 is refused.
+/// - The `Origin`, which browsers send with every request that changes something, must be this server.
+/// - API calls must be JSON. Browsers ask for permission before sending JSON to another website,
+///   which this server never gives.
+fn check_request(
+    port: u16,
+    path: &str,
+    host: Option<&str>,
+    origin: Option<&str>,
+    content_type: Option<&str>,
+) -> Result<(), ApiError> {
+    let allowed_hosts = [format!("localhost:{port}"), format!("127.0.0.1:{port}")];
+    let forbidden = |message: &str| ApiError {
+        status: 403,
+        message: message.to_owned(),
+    };
+
+    if !host.is_some_and(|host| allowed_hosts.iter().any(|allowed| allowed == host)) {
+        return Err(forbidden(&format!(
+            "Only requests to localhost:{port} are answered."
+        )));
+    }
+    if let Some(origin) = origin {
+        if !allowed_hosts
+            .iter()
+            .any(|allowed| origin == format!("http://{allowed}"))
+        {
+            return Err(forbidden("Requests from other websites are not answered."));
+        }
+    }
+    if path.starts_with("/api/")
+        && !content_type.is_some_and(|content_type| content_type.starts_with("application/json"))
+    {
+        return Err(ApiError {
+            status: 415,
+            message: "API requests must have the content type application/json.".to_owned(),
+        });
+    }
+    Ok(())
+}
+
+fn json_response(status: u16, value: &Value) -> Response<Cursor<Vec<u8>>> {
+    Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(content_type("application/json; charset=utf-8"))
+}
+
+fn respond_with(request: Request, data: &[u8], content: &str) -> Result<(), Box<dyn Error>> {
+    Ok(request.respond(Response::from_data(data).with_header(content_type(content)))?)
+}
+
+fn content_type(value: &str) -> Header {
+    Header::from_bytes("Content-Type", value).expect("content types are valid headers")
+}
+
+/// "Noto Sans" is served as `/fonts/noto-sans.ttf`.
+fn font_file_name(family: &str) -> String {
+    family.to_lowercase().replace(' ', "-")
+}
+
+#[derive(Deserialize)]
+struct TextRequest {
+    text: String,
+    /// Don't save newly generated translations.
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Deserialize)]
+struct SymbolRequest {
+    symbol: String,
+}
+
+#[derive(Deserialize)]
+struct BlockRequest {
+    word: String,
+    reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DecipherRequest {
+    text: String,
+    party: String,
+    #[serde(default)]
+    style: RevealedStyle,
+}
+
+fn api(method: &Method, path: &str, body: &str) -> Result<Value, ApiError> {
+    match (method, path) {
+        (Method::Post, "/api/translate") => {
+            let request: TextRequest = serde_json::from_str(body)?;
+            translate(&request.text, request.dry_run)
+        }
+        (Method::Post, "/api/reverse") => {
+            let request: TextRequest = serde_json::from_str(body)?;
+            let words = request
+                .text
+                .split_whitespace()
+                .map(|word| {
+                    json!({ "translation": word, "word": scrambler::reverse_translate_word(word) })
+                })
+                .collect::<Vec<_>>();
+            Ok(json!(words))
+        }
+        (Method::Post, "/api/dictionary") => {
+            let query: DictionaryQuery = serde_json::from_str(body)?;
+            let result = scrambler::search_dictionary(&query);
+            let entries = result
+                .entries
+                .iter()
+                .map(|(word, translation)| json!({ "word": word, "translation": translation }))
+                .collect::<Vec<_>>();
+            Ok(json!({
+                "entries": entries,
+                "total_matches": result.total_matches,
+                "page": result.page,
+                "page_count": result.page_count,
+            }))
+        }
+        (Method::Get, "/api/alphabet") => {
+            let symbols = scrambler::storage::load_alphabet()
+                .into_iter()
+                .map(|glyph| glyph.symbol)
+                .collect::<Vec<_>>();
+            Ok(json!(symbols))
+        }
+        (Method::Post, "/api/alphabet") => {
+            let request: SymbolRequest = serde_json::from_str(body)?;
+            let symbol = request.symbol.trim();
+            scrambler::add_to_alphabet(symbol)?;
+            Ok(Value::Null)
+        }
+        (Method::Post, "/api/alphabet/remove") => {
+            let request: SymbolRequest = serde_json::from_str(body)?;
+            scrambler::remove_from_alphabet(&request.symbol)?;
+            Ok(Value::Null)
+        }
+        (Method::Get, "/api/block-list") => Ok(json!(scrambler::blocked_translations())),
+        (Method::Post, "/api/block-list") => {
+            let request: BlockRequest = serde_json::from_str(body)?;
+            let reason = request.reason.filter(|reason| !reason.trim().is_empty());
+            scrambler::add_to_block_list(request.word.trim(), reason.as_deref())?;
+            Ok(Value::Null)
+        }
+        (Method::Post, "/api/block-list/remove") => {
+            let request: BlockRequest = serde_json::from_str(body)?;
+            scrambler::remove_from_block_list(&request.word)?;
+            Ok(Value::Null)
+        }
+        (Method::Get, "/api/parties") => Ok(json!(scrambler::parties())),
+        (Method::Post, "/api/decipher") => {
+            let request: DecipherRequest = serde_json::from_str(body)?;
+            let words = scrambler::decipher_for_party(&request.text, &request.party)?;
+            Ok(json!({
+                "text": knowledge::render(&words, request.style),
+                "words": words,
+            }))
+        }
+        _ => Err(ApiError::not_found()),
+    }
+}
+
+fn translate(text: &str, dry_run: bool) -> Result<Value, ApiError> {
+    let mut words = vec![];
+    for word in text.split_whitespace() {
+        let word = scrambler::strip_punctuation(word);
+        if word.is_empty() {
+            continue;
+        }
+        let is_new = !scrambler::is_word_known(&word);
+        let translation = scrambler::translate_word(&word)?;
+        if is_new && !dry_run {
+            scrambler::save_translation(&word, translation.clone(), false)?;
+        }
+        words.push(json!({ "word": word, "translation": translation.translation, "new": is_new }));
+    }
+    Ok(json!(words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: Option<&str> = Some("application/json");
+
+    #[test]
+    fn accept_own_page() {
+        let host = Some("localhost:8080");
+        assert!(check_request(8080, "/", host, None, None).is_ok());
+        let origin = Some("http://localhost:8080");
+        assert!(check_request(8080, "/api/block-list", host, origin, JSON).is_ok());
+    }
+
+    #[test]
+    fn reject_foreign_origin() {
+        let host = Some("localhost:8080");
+        let origin = Some("https://example.com");
+        let error = check_request(8080, "/api/alphabet/remove", host, origin, JSON).unwrap_err();
+        assert_eq!(error.status, 403);
+    }
+
+    #[test]
+    fn reject_foreign_host() {
+        let host = Some("rebound.example.com:8080");
+        let error = check_request(8080, "/api/dictionary", host, None, JSON).unwrap_err();
+        assert_eq!(error.status, 403);
+        assert!(check_request(8080, "/", None, None, None).is_err());
+    }
+
+    #[test]
+    fn reject_api_call_without_json() {
+        let host = Some("127.0.0.1:8080");
+        let text = Some("text/plain");
+        let error = check_request(8080, "/api/translate", host, None, text).unwrap_err();
+        assert_eq!(error.status, 415);
+    }
+}
@@ -0,0 +1,28 @@
+//! Fonts that are bundled with the scrambler, so the letters of the alphabet presets can be shown without relying on the fonts installed on the system.
+
+/// A font, embedded in the binary.
+pub struct BundledFont {
+    /// The family name, as stored in the font itself.
+    pub family: &'static str,
+    pub data: &'static [u8],
+}
+
+/// Noto Sans, for Latin and Greek letters. Used as default font.
+pub const NOTO_SANS: BundledFont = BundledFont {
+    family: "Noto Sans",
+    data: include_bytes!("../fonts/NotoSans-Regular.ttf"),
+};
+
+/// Tuffy, for Phoenician letters.
+pub const TUFFY: BundledFont = BundledFont {
+    family: "Tuffy",
+    data: include_bytes!("../fonts/Tuffy.ttf"),
+};
+
+/// The Greek letters and runes of GNU Unifont, for runes and the archaic Greek letters Noto Sans lacks.
+pub const UNIFONT_SUBSET: BundledFont = BundledFont {
+    family: "Unifont Subset",
+    data: include_bytes!("../fonts/UnifontSubset.ttf"),
+};
+
+pub const BUNDLED_FONTS: [BundledFont; 3] = [NOTO_SANS, TUFFY, UNIFONT_SUBSET];
@@ -1 +1,2 @@
+pub mod fonts;
 pub mod scrambler;
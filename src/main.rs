@@ -1,48 +1,530 @@
+use chrono::NaiveDate;
 use iced::alignment;
+use iced::clipboard;
+use iced::event;
+use iced::font;
+use iced::futures::channel::oneshot;
+use iced::keyboard;
+use iced::keyboard::KeyCode;
+use iced::subscription;
+use iced::theme;
 use iced::widget::button;
+use iced::widget::checkbox;
 use iced::widget::column;
 use iced::widget::container;
+use iced::widget::pick_list;
 use iced::widget::row;
 use iced::widget::scrollable;
 use iced::widget::text;
+use iced::widget::text::Shaping;
 use iced::widget::text_input;
+use iced::widget::Column;
+use iced::widget::Row;
+use iced::widget::Text;
 use iced::Application;
 use iced::Color;
 use iced::Command;
+use iced::Element;
+use iced::Event;
+use iced::Font;
 use iced::Length;
 use iced::Settings;
+use iced::Subscription;
 use itertools::Itertools;
 use log::error;
-use unicode_segmentation::UnicodeSegmentation;
+use log::warn;
+use log::Level;
+use log::Log;
+use log::Metadata;
+use log::Record;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::thread;
+use std::time::SystemTime;
 
+use ::scrambler::fonts;
+use ::scrambler::fonts::BUNDLED_FONTS;
+use ::scrambler::scrambler::block_list::BlockList;
+use ::scrambler::scrambler::block_list::BlockPattern;
+use ::scrambler::scrambler::dictionary;
+use ::scrambler::scrambler::dictionary::DictionaryPage;
+use ::scrambler::scrambler::dictionary::DictionaryQuery;
+use ::scrambler::scrambler::dictionary::DictionarySort;
+use ::scrambler::scrambler::dictionary::SearchDirection;
+use ::scrambler::scrambler::knowledge;
+use ::scrambler::scrambler::knowledge::DecipheredWord;
+use ::scrambler::scrambler::knowledge::RevealedStyle;
+use ::scrambler::scrambler::knowledge::VocabularyEntry;
+use ::scrambler::scrambler::presets;
+use ::scrambler::scrambler::presets::AlphabetPreset;
+use ::scrambler::scrambler::presets::PresetSelection;
 use ::scrambler::scrambler::Glyph;
 use ::scrambler::scrambler::Translation;
+use ::scrambler::scrambler::TranslationConflictError;
 use scrambler::scrambler;
 
+/// How many alternative translations are generated when the user asks for them.
+const CANDIDATE_COUNT: usize = 5;
+
+/// Below this window width, rows of controls are stacked, so nothing ends up out of view.
+const NARROW_WINDOW_WIDTH: u32 = 1000;
+
+/// How many letters fit on a row of the on-screen keyboard. Narrow windows get half as many.
+const KEYBOARD_ROW_LENGTH: usize = 20;
+
+/// How session dates are typed in the UI.
+const SESSION_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// At most this many known and this many unknown words are listed. Searching finds the others.
+const MAX_VOCABULARY_LENGTH: usize = 100;
+
+/// Log records of this level and worse are shown in the UI, not just in the terminal.
+const NOTIFICATION_LEVEL: Level = Level::Warn;
+
+/// Older notifications are dropped, so a flood of errors doesn't slow the UI down.
+const MAX_NOTIFICATIONS: usize = 50;
+
+/// Summaries are cut off at this many characters. The details show the whole message.
+const MAX_SUMMARY_LENGTH: usize = 100;
+
+/// Warnings and errors that were logged, but are not shown yet.
+/// They are logged from background tasks too, so the UI collects them after every update.
+static LOGGED_NOTIFICATIONS: Mutex<Vec<Notification>> = Mutex::new(Vec::new());
+
+type StorageTask = Box<dyn FnOnce() + Send>;
+
+/// Background tasks read and write the stored data on a single thread, in the order they were started.
+static STORAGE_QUEUE: OnceLock<mpsc::Sender<StorageTask>> = OnceLock::new();
+
+fn storage_queue() -> &'static mpsc::Sender<StorageTask> {
+    STORAGE_QUEUE.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<StorageTask>();
+        thread::spawn(move || {
+            for task in receiver {
+                // A task that panics must not stop the tasks after it.
+                let _ = panic::catch_unwind(AssertUnwindSafe(task));
+            }
+        });
+        sender
+    })
+}
+
 fn main() -> iced::Result {
-    env_logger::init();
-    ScramblerUi::run(Settings::default())
+    init_logger();
+    ScramblerUi::run(Settings {
+        default_font: Font::with_name(fonts::NOTO_SANS.family),
+        ..Settings::default()
+    })
+}
+
+/// Passes log records on to `env_logger`, and keeps warnings and errors so the UI can show them.
+struct NotifyingLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for NotifyingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= NOTIFICATION_LEVEL || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+        if record.level() <= NOTIFICATION_LEVEL {
+            LOGGED_NOTIFICATIONS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(Notification::new(record));
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Logs like `env_logger::init`, but warnings and errors are always kept for the notification area.
+fn init_logger() {
+    let inner = env_logger::Builder::from_default_env().build();
+    log::set_max_level(inner.filter().max(NOTIFICATION_LEVEL.to_level_filter()));
+    if let Err(error) = log::set_boxed_logger(Box::new(NotifyingLogger { inner })) {
+        eprintln!("Failed to start logging: {error}");
+    }
+}
+
+/// The way English and scrambled text are written down together, e.g. for session notes.
+fn english_scrambled_pair(english: &str, scrambled: &str) -> String {
+    format!("{english} -> {scrambled}")
+}
+
+/// Shows text that can contain letters of the alphabet.
+///
+/// Advanced shaping makes iced fall back to the bundled fonts for letters the default font doesn't have.
+fn scrambled_text<'a>(content: impl ToString) -> Text<'a> {
+    text(content).shaping(Shaping::Advanced)
 }
 
 struct ScramblerUi {
-    translated_value: Option<String>,
-    suggested_translations: Vec<(String, Translation)>,
+    current_tab: Tab,
+    window_width: u32,
+    /// The English sentence and its translation.
+    translated_value: Option<(String, String)>,
+    suggested_translations: Vec<Suggestion>,
+    translation_errors: Vec<String>,
+    /// A translation that could not be saved because the word already has a different translation.
+    translation_conflict: Option<(String, Translation, String)>,
+    block_unpicked_candidates: bool,
     input_value: String,
+    custom_word_input: String,
+    custom_translation_input: String,
+    custom_translation_error: Option<String>,
+    reverse_input: String,
+    /// The English meaning of the scrambled text. Unknown words are shown between brackets.
+    reverse_translated_value: Option<String>,
+    show_keyboard: bool,
+    keyboard_letters: KeyboardLetters,
+    keyboard_target: KeyboardTarget,
+    translation_lookup_input: String,
+    translation_being_edited: Option<TranslationEdit>,
+    translation_edit_error: Option<String>,
+    /// All known translations. Only the current page is shown, so big dictionaries stay responsive.
+    dictionary: HashMap<String, Translation>,
+    dictionary_query: DictionaryQuery,
+    dictionary_page: DictionaryPage,
     alphabet_input: String,
+    alphabet_error: Option<String>,
     current_alphabet: Vec<Glyph>,
+    /// The letter being replaced and the letter to replace it with.
+    glyph_being_edited: Option<(String, String)>,
+    /// The letter the user wants to remove and the words that use it.
+    pending_glyph_removal: Option<(String, Vec<String>)>,
+    selected_preset: Option<PresetChoice>,
+    selected_preset_groups: Vec<&'static str>,
+    preset_letters_input: String,
+    block_list: BlockList,
+    block_list_search: String,
+    block_list_input: String,
+    block_reason_input: String,
+    block_patterns: Vec<BlockPattern>,
+    block_pattern_input: String,
+    block_list_error: Option<String>,
+    /// Tells what the last undo or redo did.
+    journal_message: Option<String>,
+    party_input: String,
+    /// The party whose vocabulary is shown.
+    current_party: Option<String>,
+    parties: Vec<String>,
+    vocabulary: Vec<VocabularyEntry>,
+    reveal_input: String,
+    /// The date of the session in which words are revealed, like 2024-03-01.
+    session_input: String,
+    vocabulary_search: String,
+    players_error: Option<String>,
+    /// English text to render for the party, e.g. for a handout.
+    decipher_input: String,
+    revealed_style: RevealedStyle,
+    /// The rendered text, as the party would read it.
+    deciphered: Option<Vec<DecipheredWord>>,
+    /// Logged warnings and errors, the most recent one last.
+    notifications: Vec<Notification>,
+    /// How many background tasks are still reading or writing the stored data.
+    pending_tasks: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Translate,
+    Dictionary,
+    Alphabet,
+    BlockList,
+    Players,
+}
+
+impl Tab {
+    const ALL: [Tab; 5] = [
+        Tab::Translate,
+        Tab::Dictionary,
+        Tab::Alphabet,
+        Tab::BlockList,
+        Tab::Players,
+    ];
+}
+
+impl fmt::Display for Tab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tab::Translate => write!(f, "Translate"),
+            Tab::Dictionary => write!(f, "Dictionary"),
+            Tab::Alphabet => write!(f, "Alphabet"),
+            Tab::BlockList => write!(f, "Block list"),
+            Tab::Players => write!(f, "Players"),
+        }
+    }
+}
+
+/// A generated translation for a word without translation, waiting for the user to accept it.
+#[derive(Debug, Clone)]
+struct Suggestion {
+    word: String,
+    translation: Translation,
+    /// Locked suggestions are kept when regenerating all suggestions.
+    locked: bool,
+    /// Alternative translations the user can pick from, including the current one.
+    candidates: Vec<Translation>,
+}
+
+/// A saved translation opened in the editor, together with the changes the user made to it.
+#[derive(Debug, Clone)]
+struct TranslationEdit {
+    word: String,
+    translation: String,
+    new_word: String,
+    new_translation: String,
+    block_old: bool,
+}
+
+/// The letters shown on the on-screen keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyboardLetters {
+    Alphabet,
+    Preset(PresetChoice),
+}
+
+impl fmt::Display for KeyboardLetters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyboardLetters::Alphabet => write!(f, "Current alphabet"),
+            KeyboardLetters::Preset(preset) => write!(f, "{preset}"),
+        }
+    }
+}
+
+/// The text input the on-screen keyboard types into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyboardTarget {
+    ReverseTranslation,
+    OwnTranslation,
+}
+
+impl KeyboardTarget {
+    const ALL: [KeyboardTarget; 2] = [
+        KeyboardTarget::ReverseTranslation,
+        KeyboardTarget::OwnTranslation,
+    ];
+}
+
+impl fmt::Display for KeyboardTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyboardTarget::ReverseTranslation => write!(f, "Scrambled text"),
+            KeyboardTarget::OwnTranslation => write!(f, "Translation of your own choice"),
+        }
+    }
+}
+
+/// A logged warning or error, like a failed save or a corrupt file that was replaced by its backup.
+#[derive(Debug, Clone)]
+struct Notification {
+    level: Level,
+    time: chrono::DateTime<chrono::Local>,
+    /// The module that logged it.
+    target: String,
+    message: String,
+    show_details: bool,
+}
+
+impl Notification {
+    fn new(record: &Record) -> Self {
+        Notification {
+            level: record.level(),
+            time: chrono::Local::now(),
+            target: record.target().to_owned(),
+            message: record.args().to_string(),
+            show_details: false,
+        }
+    }
+
+    /// The first line of the message, cut off if it is long.
+    fn summary(&self) -> String {
+        let first_line = self.message.lines().next().unwrap_or("");
+        match first_line.char_indices().nth(MAX_SUMMARY_LENGTH) {
+            Some((end, _)) => format!("{}…", &first_line[..end]),
+            None => first_line.to_owned(),
+        }
+    }
+
+    fn details(&self) -> String {
+        format!(
+            "{} {} in {}:\n{}",
+            self.time.format("%Y-%m-%d %H:%M:%S"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+/// A sentence translated in the background. Words without translation become suggestions.
+#[derive(Debug, Clone)]
+struct TranslatedSentence {
+    english: String,
+    translations: Vec<Translation>,
+    suggestions: Vec<Suggestion>,
+    errors: Vec<String>,
+}
+
+/// Why a translation could not be saved. A conflict can be resolved by overwriting the saved translation.
+#[derive(Debug, Clone)]
+enum SaveError {
+    Conflict(Translation, String),
+    Failed(String),
+}
+
+impl From<Box<dyn Error>> for SaveError {
+    fn from(error: Box<dyn Error>) -> Self {
+        match error.downcast::<TranslationConflictError>() {
+            Ok(conflict) => SaveError::Conflict(conflict.new.clone(), conflict.to_string()),
+            Err(error) => SaveError::Failed(error.to_string()),
+        }
+    }
+}
+
+/// Wraps a preset so it can be shown in a pick list.
+#[derive(Debug, Clone, Copy)]
+struct PresetChoice(&'static AlphabetPreset);
+
+impl PartialEq for PresetChoice {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.id == other.0.id
+    }
+}
+
+impl Eq for PresetChoice {}
+
+impl fmt::Display for PresetChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.name)
+    }
 }
 
 #[derive(Debug, Clone)]
 enum Message {
+    TabSelected(Tab),
+    WindowResized(u32),
     InputChanged(String),
     TranslateWord,
-    TranslationAccepted(String, Translation),
-    TranslationRejected,
+    TranslationAccepted(String),
+    TranslationOverwritten,
+    TranslationConflictDismissed,
+    TranslationRejected(String),
     TranslationBlocked(String),
+    SuggestionLockToggled(String, bool),
+    ShowCandidates(String),
+    HideCandidates(String),
+    CandidatePicked(String, usize),
+    BlockUnpickedCandidatesToggled(bool),
+    AcceptAllTranslations,
+    RegenerateUnlockedTranslations,
+    CustomWordChanged(String),
+    CustomTranslationChanged(String),
+    AssignCustomTranslation,
+    ReverseInputChanged(String),
+    ReverseTranslate,
+    ReverseTranslated(String),
+    KeyboardToggled(bool),
+    KeyboardLettersSelected(KeyboardLetters),
+    KeyboardTargetSelected(KeyboardTarget),
+    KeyboardLetterPressed(String),
+    KeyboardBackspacePressed,
+    TranslationLookupChanged(String),
+    EditTranslation,
+    EditedWordChanged(String),
+    EditedTranslationChanged(String),
+    BlockOldTranslationToggled(bool),
+    SaveTranslationEdit,
+    DeleteTranslation,
+    CancelTranslationEdit,
+    DictionaryLoaded(HashMap<String, Translation>),
+    DictionarySearchChanged(String),
+    DictionaryDirectionSelected(SearchDirection),
+    DictionarySortSelected(DictionarySort),
+    DictionaryDescendingToggled(bool),
+    DictionaryPageSelected(usize),
+    OpenTranslationEdit(String),
     AlphabetInputChanged(String),
     AddToAlphabet,
     AlphabetLoaded(Vec<Glyph>),
-    DummyToMakeTextInputSelectable(String),
+    PresetSelected(PresetChoice),
+    PresetGroupToggled(&'static str, bool),
+    PresetLettersInputChanged(String),
+    ImportPreset,
+    EditGlyph(String),
+    GlyphReplacementChanged(String),
+    ReplaceGlyph,
+    CancelEditGlyph,
+    MoveGlyph(String, usize),
+    RemoveGlyph(String),
+    ConfirmRemoveGlyph,
+    CancelRemoveGlyph,
+    BlockListLoaded((BlockList, Vec<BlockPattern>)),
+    BlockListSearchChanged(String),
+    BlockListInputChanged(String),
+    BlockReasonInputChanged(String),
+    AddToBlockList,
+    Unblock(String),
+    BlockPatternInputChanged(String),
+    AddBlockPattern(fn(String) -> BlockPattern),
+    RemoveBlockPattern(BlockPattern),
+    FontLoaded(Result<(), font::Error>),
+    CopyToClipboard(String),
+    Undo,
+    Redo,
+    PartyInputChanged(String),
+    OpenParty,
+    PartySelected(String),
+    PartyLoaded(Option<String>, Vec<String>, Vec<VocabularyEntry>),
+    RevealInputChanged(String),
+    SessionInputChanged(String),
+    RevealWords,
+    RevealWord(String),
+    HideWord(String),
+    KnowledgeChanged(Result<(), String>),
+    /// The words typed in the reveal input were revealed, or not.
+    InputRevealed(String, Result<(), String>),
+    VocabularySearchChanged(String),
+    DecipherInputChanged(String),
+    RevealedStyleSelected(RevealedStyle),
+    Decipher,
+    Deciphered(Result<Vec<DecipheredWord>, String>),
+    NotificationDetailsToggled(usize),
+    DismissNotification(usize),
+    DismissAllNotifications,
+    /// A background task is done, and its result is handled like any other message.
+    TaskFinished(Box<Message>),
+    /// A background task panicked, so it has no result.
+    TaskFailed,
+    SentenceTranslated(TranslatedSentence),
+    SuggestionsSaved(Vec<(String, Result<(), SaveError>)>),
+    SuggestionRegenerated(String, Result<Translation, String>),
+    SuggestionBlocked(String, Result<(), String>, Result<Translation, String>),
+    CandidatesGenerated(String, Result<Vec<Translation>, String>),
+    CustomTranslationAssigned(String, Result<(), SaveError>),
+    /// The word and its translation as they were stored, or an error.
+    TranslationEditSaved(Result<(String, Translation), String>),
+    TranslationDeleted(Result<(), String>),
+    AlphabetChanged(Result<(), String>),
+    GlyphUsageChecked(String, Vec<String>),
+    BlockListChanged(Result<(), String>),
+    Undone(Result<Option<String>, String>),
+    Redone(Result<Option<String>, String>),
 }
 
 impl iced::Application for ScramblerUi {
@@ -55,19 +537,71 @@ impl iced::Application for ScramblerUi {
     type Flags = ();
 
     fn new(_: ()) -> (Self, Command<Message>) {
-        (
-            Self {
-                translated_value: None,
-                suggested_translations: Vec::new(),
-                input_value: "".to_owned(),
-                alphabet_input: "".to_owned(),
-                current_alphabet: Vec::new(),
-            },
-            Command::perform(
-                scrambler::storage::load_alphabet_async(),
-                Message::AlphabetLoaded,
+        let mut ui = Self {
+            current_tab: Tab::Translate,
+            window_width: iced::window::Settings::default().size.0,
+            translated_value: None,
+            suggested_translations: Vec::new(),
+            translation_errors: Vec::new(),
+            translation_conflict: None,
+            block_unpicked_candidates: false,
+            input_value: "".to_owned(),
+            custom_word_input: "".to_owned(),
+            custom_translation_input: "".to_owned(),
+            custom_translation_error: None,
+            reverse_input: "".to_owned(),
+            reverse_translated_value: None,
+            show_keyboard: false,
+            keyboard_letters: KeyboardLetters::Alphabet,
+            keyboard_target: KeyboardTarget::ReverseTranslation,
+            translation_lookup_input: "".to_owned(),
+            translation_being_edited: None,
+            translation_edit_error: None,
+            dictionary: HashMap::new(),
+            dictionary_query: DictionaryQuery::default(),
+            dictionary_page: DictionaryPage::default(),
+            alphabet_input: "".to_owned(),
+            alphabet_error: None,
+            current_alphabet: Vec::new(),
+            glyph_being_edited: None,
+            pending_glyph_removal: None,
+            selected_preset: None,
+            selected_preset_groups: Vec::new(),
+            preset_letters_input: "".to_owned(),
+            block_list: BlockList::new(),
+            block_list_search: "".to_owned(),
+            block_list_input: "".to_owned(),
+            block_reason_input: "".to_owned(),
+            block_patterns: Vec::new(),
+            block_pattern_input: "".to_owned(),
+            block_list_error: None,
+            journal_message: None,
+            party_input: "".to_owned(),
+            current_party: None,
+            parties: Vec::new(),
+            vocabulary: Vec::new(),
+            reveal_input: "".to_owned(),
+            session_input: chrono::Local::now()
+                .date_naive()
+                .format(SESSION_DATE_FORMAT)
+                .to_string(),
+            vocabulary_search: "".to_owned(),
+            players_error: None,
+            decipher_input: "".to_owned(),
+            revealed_style: RevealedStyle::default(),
+            deciphered: None,
+            notifications: Vec::new(),
+            pending_tasks: 0,
+        };
+        let command = Command::batch([
+            ui.load_alphabet(),
+            ui.load_block_list(),
+            ui.refresh_dictionary(),
+            Command::batch(
+                BUNDLED_FONTS.map(|bundled| font::load(bundled.data).map(Message::FontLoaded)),
             ),
-        )
+        ]);
+        (ui, command)
     }
 
     fn title(&self) -> String {
@@ -75,56 +609,721 @@ impl iced::Application for ScramblerUi {
     }
 
     fn update(&mut self, message: Self::Message) -> Command<Message> {
+        let command = self.handle(message);
+        self.collect_notifications();
+        command
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        subscription::events_with(|event, _: event::Status| match event {
+            Event::Window(iced::window::Event::Resized { width, .. }) => {
+                Some(Message::WindowResized(width))
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if modifiers.command() => match key_code {
+                KeyCode::Z if modifiers.shift() => Some(Message::Redo),
+                KeyCode::Z => Some(Message::Undo),
+                KeyCode::Y => Some(Message::Redo),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    fn view(&self) -> Element<'_, Self::Message> {
+        let title = text("Scrambler")
+            .width(Length::Fill)
+            .size(40)
+            .style(Color::from([0.5, 0.5, 0.5]))
+            .horizontal_alignment(alignment::Horizontal::Center);
+
+        let tab_bar = Row::with_children(
+            Tab::ALL
+                .iter()
+                .map(|tab| {
+                    let style = match *tab == self.current_tab {
+                        true => theme::Button::Primary,
+                        false => theme::Button::Secondary,
+                    };
+                    button(text(tab.to_string()))
+                        .style(style)
+                        .on_press(Message::TabSelected(*tab))
+                        .into()
+                })
+                .collect(),
+        )
+        .push(button("Undo").on_press(Message::Undo))
+        .push(button("Redo").on_press(Message::Redo))
+        .push(text(self.journal_message.as_deref().unwrap_or("")))
+        .push(text(match self.pending_tasks {
+            0 => "",
+            _ => "Working…",
+        }))
+        .spacing(10)
+        .align_items(alignment::Alignment::Center);
+
+        let tab_content = match self.current_tab {
+            Tab::Translate => self.translate_tab(),
+            Tab::Dictionary => self.dictionary_tab(),
+            Tab::Alphabet => self.alphabet_tab(),
+            Tab::BlockList => self.block_list_tab(),
+            Tab::Players => self.players_tab(),
+        };
+
+        let mut content = column![title, tab_bar].spacing(20);
+        if !self.notifications.is_empty() {
+            content = content.push(self.notifications_view());
+        }
+        let content = content.push(tab_content);
+
+        scrollable(container(content).width(Length::Fill).padding(20)).into()
+    }
+}
+
+impl ScramblerUi {
+    fn handle(&mut self, message: Message) -> Command<Message> {
         match message {
+            Message::TaskFinished(message) => {
+                self.pending_tasks -= 1;
+                return self.handle(*message);
+            }
+            Message::TaskFailed => {
+                self.pending_tasks -= 1;
+                error!(
+                    "A background task failed unexpectedly. Its changes may not have been saved."
+                );
+                return self.refresh_dictionary();
+            }
+            Message::TabSelected(tab) => {
+                self.current_tab = tab;
+                // The vocabulary changes with every translation, so it's reloaded when it is shown.
+                if tab == Tab::Players {
+                    return self.load_party();
+                }
+            }
+            Message::WindowResized(width) => self.window_width = width,
             Message::InputChanged(value) => {
                 self.input_value = value;
             }
-            Message::TranslateWord => self.translate_input(),
-            Message::TranslationAccepted(original, translation) => {
-                if let Err(error) = scrambler::save_translation(&original, translation) {
+            Message::TranslateWord => return self.translate_input(),
+            Message::SentenceTranslated(sentence) => {
+                self.translation_errors = sentence.errors;
+                for error in self.translation_errors.iter() {
                     error!("{error}");
                 }
-                self.translate_input();
+                if sentence.suggestions.is_empty() {
+                    self.translated_value = Some((
+                        sentence.english,
+                        sentence
+                            .translations
+                            .iter()
+                            .map(|translation| &translation.translation)
+                            .join(" "),
+                    ));
+                    self.suggested_translations = Vec::new();
+                } else {
+                    self.translated_value = None;
+                    self.suggested_translations = sentence.suggestions;
+                }
+            }
+            Message::TranslationAccepted(word) => {
+                self.translation_conflict = None;
+                return self.save_suggestions(vec![word]);
+            }
+            Message::SuggestionsSaved(results) => {
+                for (word, result) in results {
+                    match result {
+                        Ok(()) => self
+                            .suggested_translations
+                            .retain(|suggestion| suggestion.word != word),
+                        Err(SaveError::Conflict(translation, message)) => {
+                            error!("{message}");
+                            self.translation_conflict = Some((word, translation, message));
+                        }
+                        Err(SaveError::Failed(message)) => {
+                            error!("{message}");
+                            self.translation_errors.push(message);
+                        }
+                    }
+                }
+                return self.finish_suggestions();
+            }
+            Message::TranslationOverwritten => {
+                if let Some((original, translation, _)) = self.translation_conflict.take() {
+                    return self.background(
+                        move || {
+                            let result = scrambler::save_translation(&original, translation, true)
+                                .map_err(SaveError::from);
+                            vec![(original, result)]
+                        },
+                        Message::SuggestionsSaved,
+                    );
+                }
+            }
+            Message::TranslationConflictDismissed => self.translation_conflict = None,
+            Message::TranslationRejected(word) => return self.regenerate_suggestion(word),
+            Message::SuggestionRegenerated(word, result) => {
+                self.apply_regenerated_suggestion(&word, result)
             }
-            Message::TranslationRejected => self.translate_input(),
             Message::TranslationBlocked(word) => {
-                if let Err(error) = scrambler::add_to_block_list(&word) {
+                if let Some(suggestion) = self.find_suggestion(&word) {
+                    let blocked = suggestion.translation.translation.clone();
+                    return self.background(
+                        move || {
+                            let reason = format!("Rejected as translation of \"{word}\"");
+                            let block_result =
+                                scrambler::add_to_block_list(&blocked, Some(&reason))
+                                    .map_err(|error| error.to_string());
+                            let translation =
+                                scrambler::translate_word(&word).map_err(|error| error.to_string());
+                            (word, block_result, translation)
+                        },
+                        |(word, block_result, translation)| {
+                            Message::SuggestionBlocked(word, block_result, translation)
+                        },
+                    );
+                }
+            }
+            Message::SuggestionBlocked(word, block_result, translation) => {
+                self.apply_regenerated_suggestion(&word, translation);
+                return self.apply_block_list_change(block_result);
+            }
+            Message::SuggestionLockToggled(word, locked) => {
+                if let Some(suggestion) = self.find_suggestion_mut(&word) {
+                    suggestion.locked = locked;
+                }
+            }
+            Message::ShowCandidates(word) => {
+                return self.background(
+                    move || {
+                        let candidates = scrambler::translation_candidates(&word, CANDIDATE_COUNT)
+                            .map_err(|error| error.to_string());
+                        (word, candidates)
+                    },
+                    |(word, candidates)| Message::CandidatesGenerated(word, candidates),
+                );
+            }
+            Message::CandidatesGenerated(word, result) => match result {
+                Ok(mut candidates) => {
+                    if let Some(suggestion) = self.find_suggestion_mut(&word) {
+                        let current = suggestion.translation.clone();
+                        candidates.retain(|candidate| candidate.translation != current.translation);
+                        candidates.insert(0, current);
+                        suggestion.candidates = candidates;
+                    }
+                }
+                Err(error) => {
+                    error!("{error}");
+                    self.translation_errors.push(error);
+                }
+            },
+            Message::HideCandidates(word) => {
+                if let Some(suggestion) = self.find_suggestion_mut(&word) {
+                    suggestion.candidates = Vec::new();
+                }
+            }
+            Message::CandidatePicked(word, index) => {
+                let block_unpicked = self.block_unpicked_candidates;
+                if let Some(suggestion) = self.find_suggestion_mut(&word) {
+                    // The candidates may have changed since the button was drawn.
+                    let Some(picked) = suggestion.candidates.get(index).cloned() else {
+                        return Command::none();
+                    };
+                    suggestion.translation = picked;
+                    let candidates = std::mem::take(&mut suggestion.candidates);
+
+                    if block_unpicked {
+                        return self.background(
+                            move || {
+                                let reason = format!("Rejected as translation of \"{word}\"");
+                                let mut result = Ok(());
+                                for (_, candidate) in candidates
+                                    .iter()
+                                    .enumerate()
+                                    .filter(|(other, _)| *other != index)
+                                {
+                                    result = result.and_then(|_| {
+                                        scrambler::add_to_block_list(
+                                            &candidate.translation,
+                                            Some(&reason),
+                                        )
+                                    });
+                                }
+                                result.map_err(|error| error.to_string())
+                            },
+                            Message::BlockListChanged,
+                        );
+                    }
+                }
+            }
+            Message::BlockUnpickedCandidatesToggled(checked) => {
+                self.block_unpicked_candidates = checked
+            }
+            Message::AcceptAllTranslations => {
+                self.translation_conflict = None;
+                let words = self
+                    .suggested_translations
+                    .iter()
+                    .map(|suggestion| suggestion.word.clone())
+                    .collect_vec();
+                return self.save_suggestions(words);
+            }
+            Message::RegenerateUnlockedTranslations => {
+                let words = self
+                    .suggested_translations
+                    .iter()
+                    .filter(|suggestion| !suggestion.locked)
+                    .map(|suggestion| suggestion.word.clone())
+                    .collect_vec();
+                return Command::batch(
+                    words
+                        .into_iter()
+                        .map(|word| self.regenerate_suggestion(word))
+                        .collect_vec(),
+                );
+            }
+            Message::CustomWordChanged(value) => self.custom_word_input = value,
+            Message::CustomTranslationChanged(value) => self.custom_translation_input = value,
+            Message::AssignCustomTranslation => {
+                self.custom_translation_error = None;
+                self.translation_conflict = None;
+                let word = scrambler::strip_punctuation(self.custom_word_input.trim());
+                let translation = self.custom_translation_input.trim().to_owned();
+                return self.background(
+                    move || {
+                        let result = scrambler::assign_translation(&word, &translation, false)
+                            .map(|_| ())
+                            .map_err(SaveError::from);
+                        (word, result)
+                    },
+                    |(word, result)| Message::CustomTranslationAssigned(word, result),
+                );
+            }
+            Message::CustomTranslationAssigned(word, result) => match result {
+                Ok(()) => {
+                    self.custom_word_input = "".to_owned();
+                    self.custom_translation_input = "".to_owned();
+                    self.suggested_translations
+                        .retain(|suggestion| suggestion.word != word);
+                    return self.finish_suggestions();
+                }
+                Err(SaveError::Conflict(translation, message)) => {
+                    error!("{message}");
+                    self.translation_conflict = Some((word, translation, message));
+                }
+                Err(SaveError::Failed(message)) => {
+                    error!("{message}");
+                    self.custom_translation_error = Some(message);
+                }
+            },
+            Message::ReverseInputChanged(value) => self.reverse_input = value,
+            Message::ReverseTranslate => {
+                let scrambled = self.reverse_input.clone();
+                return self.background(
+                    move || reverse_translate_sentence(&scrambled),
+                    Message::ReverseTranslated,
+                );
+            }
+            Message::ReverseTranslated(english) => self.reverse_translated_value = Some(english),
+            Message::KeyboardToggled(show) => self.show_keyboard = show,
+            Message::KeyboardLettersSelected(letters) => self.keyboard_letters = letters,
+            Message::KeyboardTargetSelected(target) => self.keyboard_target = target,
+            Message::KeyboardLetterPressed(letter) => self.keyboard_input().push_str(&letter),
+            Message::KeyboardBackspacePressed => {
+                // A letter can be more than one grapheme, like "th". It is removed as a whole.
+                let alphabet = self.current_alphabet.clone();
+                let input = self.keyboard_input();
+                let last_length = scrambler::split_into_glyphs(input, &alphabet)
+                    .last()
+                    .map_or(0, |glyph| glyph.len());
+                input.truncate(input.len() - last_length);
+            }
+            Message::TranslationLookupChanged(value) => self.translation_lookup_input = value,
+            Message::EditTranslation => {
+                let word = self.translation_lookup_input.trim().to_owned();
+                self.open_translation_edit(&word);
+            }
+            Message::EditedWordChanged(value) => {
+                if let Some(edit) = &mut self.translation_being_edited {
+                    edit.new_word = value;
+                }
+            }
+            Message::EditedTranslationChanged(value) => {
+                if let Some(edit) = &mut self.translation_being_edited {
+                    edit.new_translation = value;
+                }
+            }
+            Message::BlockOldTranslationToggled(checked) => {
+                if let Some(edit) = &mut self.translation_being_edited {
+                    edit.block_old = checked;
+                }
+            }
+            Message::SaveTranslationEdit => {
+                if let Some(edit) = self.translation_being_edited.clone() {
+                    return self.background(
+                        move || {
+                            scrambler::edit_translation(
+                                &edit.word,
+                                &edit.new_word,
+                                &edit.new_translation,
+                                edit.block_old,
+                            )
+                            .map_err(|error| error.to_string())
+                        },
+                        Message::TranslationEditSaved,
+                    );
+                }
+            }
+            Message::TranslationEditSaved(result) => {
+                self.translation_edit_error = None;
+                match result {
+                    Ok((word, translation)) => {
+                        let translation = translation.translation;
+                        self.translation_being_edited = Some(TranslationEdit {
+                            word: word.clone(),
+                            translation: translation.clone(),
+                            new_word: word,
+                            new_translation: translation,
+                            block_old: false,
+                        });
+                    }
+                    Err(error) => {
+                        error!("{error}");
+                        self.translation_edit_error = Some(error);
+                    }
+                }
+                return Command::batch([self.load_block_list(), self.refresh_dictionary()]);
+            }
+            Message::DeleteTranslation => {
+                if let Some(edit) = self.translation_being_edited.take() {
+                    return self.background(
+                        move || {
+                            scrambler::delete_translation(&edit.word, edit.block_old)
+                                .map_err(|error| error.to_string())
+                        },
+                        Message::TranslationDeleted,
+                    );
+                }
+            }
+            Message::TranslationDeleted(result) => {
+                self.translation_edit_error = None;
+                if let Err(error) = result {
                     error!("{error}");
+                    self.translation_edit_error = Some(error);
                 }
-                self.translate_input();
+                return Command::batch([self.load_block_list(), self.refresh_dictionary()]);
+            }
+            Message::CancelTranslationEdit => {
+                self.translation_being_edited = None;
+                self.translation_edit_error = None;
+            }
+            Message::DictionaryLoaded(dictionary) => {
+                self.dictionary = dictionary;
+                self.query_dictionary();
+            }
+            Message::DictionarySearchChanged(value) => {
+                self.dictionary_query.search = value;
+                self.dictionary_query.page = 0;
+                self.query_dictionary();
+            }
+            Message::DictionaryDirectionSelected(direction) => {
+                self.dictionary_query.direction = direction;
+                self.dictionary_query.page = 0;
+                self.query_dictionary();
+            }
+            Message::DictionarySortSelected(sort) => {
+                self.dictionary_query.sort = sort;
+                self.query_dictionary();
+            }
+            Message::DictionaryDescendingToggled(descending) => {
+                self.dictionary_query.descending = descending;
+                self.query_dictionary();
+            }
+            Message::DictionaryPageSelected(page) => {
+                self.dictionary_query.page = page;
+                self.query_dictionary();
+            }
+            Message::OpenTranslationEdit(word) => {
+                self.translation_lookup_input = word.clone();
+                self.open_translation_edit(&word);
             }
             Message::AlphabetInputChanged(value) => {
-                let char_count = value.graphemes(true).count();
-                // We only accept a single non-whitespace character.
-                // And of course an empty field. Otherwise, people can't delete the character.
-                if char_count == 0 || (char_count == 1 && !value.trim().is_empty()) {
+                // A letter can consist of multiple characters, like "th". But it can't contain whitespace.
+                if !value.chars().any(char::is_whitespace) {
                     self.alphabet_input = value;
                 }
             }
             Message::AddToAlphabet => {
                 if !self.alphabet_input.is_empty() {
-                    if let Err(error) = scrambler::add_to_alphabet(&self.alphabet_input) {
-                        error!("{}", error.to_string());
-                    }
-                    self.alphabet_input = "".to_owned();
+                    let letter = std::mem::take(&mut self.alphabet_input);
+                    return self.change_alphabet(move || scrambler::add_to_alphabet(&letter));
+                }
+            }
+            Message::AlphabetLoaded(alphabet_result) => {
+                self.current_alphabet = alphabet_result;
+            }
+            Message::AlphabetChanged(result) => return self.apply_alphabet_change(result),
+            Message::PresetSelected(preset) => {
+                self.selected_preset = Some(preset);
+                self.selected_preset_groups = Vec::new();
+                self.preset_letters_input = "".to_owned();
+            }
+            Message::PresetGroupToggled(group, checked) => {
+                self.selected_preset_groups
+                    .retain(|selected| *selected != group);
+                if checked {
+                    self.selected_preset_groups.push(group);
+                }
+            }
+            Message::PresetLettersInputChanged(value) => self.preset_letters_input = value,
+            Message::ImportPreset => {
+                if let Some(preset) = self.selected_preset {
+                    let selection = if !self.preset_letters_input.trim().is_empty() {
+                        PresetSelection::Letters(
+                            self.preset_letters_input
+                                .split_whitespace()
+                                .map(str::to_owned)
+                                .collect(),
+                        )
+                    } else if !self.selected_preset_groups.is_empty() {
+                        PresetSelection::Groups(
+                            self.selected_preset_groups
+                                .iter()
+                                .map(|group| group.to_string())
+                                .collect(),
+                        )
+                    } else {
+                        PresetSelection::All
+                    };
 
-                    self.current_alphabet = scrambler::storage::load_alphabet();
+                    return self.change_alphabet(move || {
+                        scrambler::import_preset(preset.0.id, &selection).map(|_| ())
+                    });
+                }
+            }
+            Message::EditGlyph(symbol) => {
+                self.glyph_being_edited = Some((symbol.clone(), symbol));
+            }
+            Message::GlyphReplacementChanged(value) => {
+                if let Some((_, replacement)) = &mut self.glyph_being_edited {
+                    if !value.chars().any(char::is_whitespace) {
+                        *replacement = value;
+                    }
+                }
+            }
+            Message::ReplaceGlyph => {
+                if let Some((symbol, replacement)) = self.glyph_being_edited.take() {
+                    return self.change_alphabet(move || {
+                        scrambler::replace_in_alphabet(&symbol, &replacement)
+                    });
+                }
+            }
+            Message::CancelEditGlyph => self.glyph_being_edited = None,
+            Message::MoveGlyph(symbol, new_index) => {
+                return self
+                    .change_alphabet(move || scrambler::move_in_alphabet(&symbol, new_index));
+            }
+            Message::RemoveGlyph(symbol) => {
+                return self.background(
+                    move || {
+                        let words = scrambler::translations_using_glyph(&symbol);
+                        (symbol, words)
+                    },
+                    |(symbol, words)| Message::GlyphUsageChecked(symbol, words),
+                );
+            }
+            Message::GlyphUsageChecked(symbol, words) => {
+                if words.is_empty() {
+                    return self.change_alphabet(move || scrambler::remove_from_alphabet(&symbol));
+                }
+                self.pending_glyph_removal = Some((symbol, words));
+            }
+            Message::ConfirmRemoveGlyph => {
+                if let Some((symbol, _)) = self.pending_glyph_removal.take() {
+                    return self.change_alphabet(move || scrambler::remove_from_alphabet(&symbol));
+                }
+            }
+            Message::CancelRemoveGlyph => self.pending_glyph_removal = None,
+            Message::BlockListLoaded((block_list, block_patterns)) => {
+                self.block_list = block_list;
+                self.block_patterns = block_patterns;
+            }
+            Message::BlockListChanged(result) => return self.apply_block_list_change(result),
+            Message::BlockListSearchChanged(value) => {
+                self.block_list_search = value;
+                return self.load_block_list();
+            }
+            Message::BlockListInputChanged(value) => self.block_list_input = value,
+            Message::BlockReasonInputChanged(value) => self.block_reason_input = value,
+            Message::AddToBlockList => {
+                let word = self.block_list_input.trim().to_owned();
+                if !word.is_empty() {
+                    let reason = self.block_reason_input.trim().to_owned();
+                    self.block_list_input = "".to_owned();
+                    self.block_reason_input = "".to_owned();
+                    return self.change_block_list(move || {
+                        let reason = (!reason.is_empty()).then_some(reason.as_str());
+                        scrambler::add_to_block_list(&word, reason)
+                    });
                 }
             }
-            Message::AlphabetLoaded(alphabet_result) => self.current_alphabet = alphabet_result,
-            Message::DummyToMakeTextInputSelectable(_) => {}
+            Message::Unblock(word) => {
+                return self.change_block_list(move || scrambler::remove_from_block_list(&word));
+            }
+            Message::BlockPatternInputChanged(value) => self.block_pattern_input = value,
+            Message::AddBlockPattern(create_pattern) => {
+                let pattern = create_pattern(self.block_pattern_input.clone());
+                if let Err(error) = pattern.validate() {
+                    error!("{error}");
+                    self.block_list_error = Some(error.to_string());
+                } else {
+                    self.block_pattern_input = "".to_owned();
+                    return self.change_block_list(move || scrambler::add_block_pattern(pattern));
+                }
+            }
+            Message::RemoveBlockPattern(pattern) => {
+                return self.change_block_list(move || scrambler::remove_block_pattern(&pattern));
+            }
+            Message::FontLoaded(result) => {
+                if let Err(error) = result {
+                    error!("Failed to load a bundled font: {error:?}");
+                }
+            }
+            Message::CopyToClipboard(contents) => return clipboard::write(contents),
+            Message::Undo => {
+                return self.background(
+                    || scrambler::undo().map_err(|error| error.to_string()),
+                    Message::Undone,
+                )
+            }
+            Message::Redo => {
+                return self.background(
+                    || scrambler::redo().map_err(|error| error.to_string()),
+                    Message::Redone,
+                )
+            }
+            Message::PartyInputChanged(value) => self.party_input = value,
+            Message::OpenParty => {
+                let party = self.party_input.trim().to_owned();
+                return self.open_party(party);
+            }
+            Message::PartySelected(party) => {
+                self.party_input = party.clone();
+                return self.open_party(party);
+            }
+            Message::PartyLoaded(party, parties, vocabulary) => {
+                self.parties = parties;
+                // Another party may have been opened in the meantime.
+                if party == self.current_party {
+                    self.vocabulary = vocabulary;
+                }
+            }
+            Message::RevealInputChanged(value) => self.reveal_input = value,
+            Message::SessionInputChanged(value) => self.session_input = value,
+            Message::RevealWords => {
+                let words = self
+                    .reveal_input
+                    .split_whitespace()
+                    .map(str::to_owned)
+                    .collect_vec();
+                if !words.is_empty() {
+                    let input = self.reveal_input.clone();
+                    return self
+                        .reveal_words(words, move |result| Message::InputRevealed(input, result));
+                }
+            }
+            Message::RevealWord(word) => {
+                return self.reveal_words(vec![word], Message::KnowledgeChanged)
+            }
+            Message::HideWord(word) => {
+                if let Some(party) = self.current_party.clone() {
+                    return self.background(
+                        move || {
+                            scrambler::hide_words(&party, &[word])
+                                .map(|_| ())
+                                .map_err(|error| error.to_string())
+                        },
+                        Message::KnowledgeChanged,
+                    );
+                }
+            }
+            Message::KnowledgeChanged(result) => {
+                self.players_error = None;
+                if let Err(error) = result {
+                    error!("{error}");
+                    self.players_error = Some(error);
+                }
+                return self.load_party();
+            }
+            Message::InputRevealed(input, result) => {
+                // Only clear what was revealed, not what was typed in the meantime.
+                if result.is_ok() && self.reveal_input == input {
+                    self.reveal_input = "".to_owned();
+                }
+                return self.handle(Message::KnowledgeChanged(result));
+            }
+            Message::VocabularySearchChanged(value) => self.vocabulary_search = value,
+            Message::DecipherInputChanged(value) => self.decipher_input = value,
+            Message::RevealedStyleSelected(style) => self.revealed_style = style,
+            Message::Decipher => {
+                if let Some(party) = self.current_party.clone() {
+                    let text = self.decipher_input.clone();
+                    return self.background(
+                        move || {
+                            scrambler::decipher_for_party(&text, &party)
+                                .map_err(|error| error.to_string())
+                        },
+                        Message::Deciphered,
+                    );
+                }
+            }
+            Message::Deciphered(result) => {
+                self.players_error = None;
+                self.deciphered = None;
+                match result {
+                    Ok(words) => self.deciphered = Some(words),
+                    Err(error) => {
+                        error!("{error}");
+                        self.players_error = Some(error);
+                    }
+                }
+            }
+            Message::NotificationDetailsToggled(index) => {
+                if let Some(notification) = self.notifications.get_mut(index) {
+                    notification.show_details = !notification.show_details;
+                }
+            }
+            Message::DismissNotification(index) => {
+                if index < self.notifications.len() {
+                    self.notifications.remove(index);
+                }
+            }
+            Message::DismissAllNotifications => self.notifications.clear(),
+            Message::Undone(result) => {
+                return self.apply_journal_step(result, "Undid", "Nothing to undo.")
+            }
+            Message::Redone(result) => {
+                return self.apply_journal_step(result, "Redid", "Nothing to redo.")
+            }
         }
 
         Command::none()
     }
 
-    fn view(&self) -> iced::Element<'_, Self::Message> {
-        let title = text("Scrambler")
-            .width(Length::Fill)
-            .size(100)
-            .style(Color::from([0.5, 0.5, 0.5]))
-            .horizontal_alignment(alignment::Horizontal::Center);
+    /// Puts controls next to each other, or below each other when the window is too narrow for that.
+    fn controls<'a>(&self, controls: Vec<Element<'a, Message>>) -> Element<'a, Message> {
+        match self.window_width < NARROW_WINDOW_WIDTH {
+            true => Column::with_children(controls).spacing(10).into(),
+            false => Row::with_children(controls)
+                .spacing(10)
+                .align_items(alignment::Alignment::Center)
+                .into(),
+        }
+    }
 
+    fn translate_tab(&self) -> Element<'_, Message> {
         let input = text_input("What needs to be translated?", &self.input_value)
             .on_input(Message::InputChanged)
             .on_submit(Message::TranslateWord)
@@ -132,48 +1331,391 @@ impl iced::Application for ScramblerUi {
             .size(30);
 
         let translation;
-        if let Some(value) = &self.translated_value {
-            translation =
-                row![text_input("", &value).on_input(Message::DummyToMakeTextInputSelectable)]
-                    .spacing(10);
+        if let Some((english, value)) = &self.translated_value {
+            translation = row![
+                scrambled_text(value),
+                button("Copy").on_press(Message::CopyToClipboard(value.clone())),
+                button("Copy as English/scrambled pair").on_press(Message::CopyToClipboard(
+                    english_scrambled_pair(english, value)
+                ))
+            ]
+            .spacing(10)
+            .align_items(alignment::Alignment::Center);
         } else {
             translation = row![];
         }
 
-        let mut suggested_translations_view = column![];
+        let mut suggested_translations_view = column![].spacing(10);
         if !self.suggested_translations.is_empty() {
-            for value in self.suggested_translations.iter() {
-                let accept_button = button("Accept translation").on_press(
-                    Message::TranslationAccepted(value.0.clone(), value.1.clone()),
-                );
-                let reset_button =
-                    button("Generate new translation").on_press(Message::TranslationRejected);
-                let block_button = button("Block translation and generate a new one")
-                    .on_press(Message::TranslationBlocked(value.1.translation.clone()));
-                suggested_translations_view = suggested_translations_view.push(
+            suggested_translations_view = suggested_translations_view.push(self.controls(vec![
+                button("Accept all translations")
+                    .on_press(Message::AcceptAllTranslations)
+                    .into(),
+                button("Generate new translations for all unlocked words")
+                    .on_press(Message::RegenerateUnlockedTranslations)
+                    .into(),
+                checkbox(
+                    "Block the alternatives I don't pick",
+                    self.block_unpicked_candidates,
+                    Message::BlockUnpickedCandidatesToggled,
+                )
+                .into(),
+            ]));
+            for suggestion in self.suggested_translations.iter() {
+                let word = &suggestion.word;
+                let accept_button = button("Accept translation")
+                    .on_press(Message::TranslationAccepted(word.clone()));
+                let mut reset_button = button("Generate new translation");
+                let mut block_button = button("Block translation and generate a new one");
+                if !suggestion.locked {
+                    reset_button =
+                        reset_button.on_press(Message::TranslationRejected(word.clone()));
+                    block_button = block_button.on_press(Message::TranslationBlocked(word.clone()));
+                }
+                let lock_checkbox = checkbox("Lock", suggestion.locked, move |locked| {
+                    Message::SuggestionLockToggled(word.clone(), locked)
+                });
+                let candidates_button =
+                    match suggestion.candidates.is_empty() {
+                        true => button("Show alternatives")
+                            .on_press(Message::ShowCandidates(word.clone())),
+                        false => button("Hide alternatives")
+                            .on_press(Message::HideCandidates(word.clone())),
+                    };
+                let scrambled = &suggestion.translation.translation;
+                let suggestion_row = row![
+                    text(word),
+                    text("->"),
+                    scrambled_text(scrambled),
+                    button("Copy").on_press(Message::CopyToClipboard(scrambled.clone())),
+                    button("Copy pair").on_press(Message::CopyToClipboard(english_scrambled_pair(
+                        word, scrambled
+                    )))
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center);
+                let suggestion_controls = self.controls(vec![
+                    lock_checkbox.into(),
+                    accept_button.into(),
+                    reset_button.into(),
+                    block_button.into(),
+                    candidates_button.into(),
+                ]);
+                suggested_translations_view = suggested_translations_view
+                    .push(self.controls(vec![suggestion_row.into(), suggestion_controls]));
+
+                if !suggestion.candidates.is_empty() {
+                    let mut candidates_view = row![].spacing(20);
+                    for (index, candidate) in suggestion.candidates.iter().enumerate() {
+                        candidates_view = candidates_view.push(
+                            column![
+                                scrambled_text(&candidate.translation),
+                                button("Pick")
+                                    .on_press(Message::CandidatePicked(word.clone(), index))
+                            ]
+                            .spacing(5)
+                            .width(150),
+                        );
+                    }
+                    suggested_translations_view = suggested_translations_view.push(candidates_view);
+                }
+            }
+        }
+
+        let mut translation_errors_view = column![];
+        for error in self.translation_errors.iter() {
+            translation_errors_view =
+                translation_errors_view.push(text(error).style(Color::from([0.8, 0.0, 0.0])));
+        }
+
+        let translation_conflict_view = match &self.translation_conflict {
+            Some((_, _, message)) => column![
+                text(message).style(Color::from([0.8, 0.4, 0.0])),
+                row![
+                    button("Overwrite").on_press(Message::TranslationOverwritten),
+                    button("Keep the existing translation")
+                        .on_press(Message::TranslationConflictDismissed)
+                ]
+                .spacing(10)
+            ]
+            .spacing(10),
+            None => column![],
+        };
+
+        let mut custom_translation_view = column![self.controls(vec![
+            text_input("English word", &self.custom_word_input)
+                .on_input(Message::CustomWordChanged)
+                .on_submit(Message::AssignCustomTranslation)
+                .into(),
+            text("->").into(),
+            text_input(
+                "Translation of your own choice",
+                &self.custom_translation_input,
+            )
+            .on_input(Message::CustomTranslationChanged)
+            .on_submit(Message::AssignCustomTranslation)
+            .into(),
+            button("Save translation")
+                .on_press(Message::AssignCustomTranslation)
+                .into(),
+        ])]
+        .spacing(10);
+        if let Some(error) = &self.custom_translation_error {
+            custom_translation_view =
+                custom_translation_view.push(text(error).style(Color::from([0.8, 0.0, 0.0])));
+        }
+
+        let mut reverse_translation_view = column![self.controls(vec![
+            text_input(
+                "Scrambled text to translate back to English",
+                &self.reverse_input
+            )
+            .on_input(Message::ReverseInputChanged)
+            .on_submit(Message::ReverseTranslate)
+            .into(),
+            button("Translate back")
+                .on_press(Message::ReverseTranslate)
+                .into(),
+        ])]
+        .spacing(10);
+        if let Some(english) = &self.reverse_translated_value {
+            reverse_translation_view = reverse_translation_view.push(
+                row![
+                    text(english),
+                    button("Copy").on_press(Message::CopyToClipboard(english.clone()))
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center),
+            );
+        }
+
+        let mut keyboard_view = column![checkbox(
+            "Show a keyboard with the letters",
+            self.show_keyboard,
+            Message::KeyboardToggled
+        )]
+        .spacing(10);
+        if self.show_keyboard {
+            keyboard_view = keyboard_view.push(self.keyboard());
+        }
+
+        column![
+            input,
+            translation,
+            suggested_translations_view,
+            translation_errors_view,
+            translation_conflict_view,
+            custom_translation_view,
+            reverse_translation_view,
+            keyboard_view
+        ]
+        .spacing(20)
+        .into()
+    }
+
+    /// Clickable letters, so scrambled text can be typed without a special keyboard layout.
+    fn keyboard(&self) -> Element<'_, Message> {
+        let letters = match self.keyboard_letters {
+            KeyboardLetters::Alphabet => self
+                .current_alphabet
+                .iter()
+                .map(|glyph| glyph.symbol.clone())
+                .collect_vec(),
+            KeyboardLetters::Preset(preset) => preset
+                .0
+                .letters
+                .iter()
+                .map(|letter| letter.symbol.to_owned())
+                .collect_vec(),
+        };
+        let letters_per_row = match self.window_width < NARROW_WINDOW_WIDTH {
+            true => KEYBOARD_ROW_LENGTH / 2,
+            false => KEYBOARD_ROW_LENGTH,
+        };
+        let keys = Column::with_children(
+            letters
+                .chunks(letters_per_row)
+                .map(|chunk| {
+                    Row::with_children(
+                        chunk
+                            .iter()
+                            .map(|letter| {
+                                button(scrambled_text(letter))
+                                    .width(40)
+                                    .on_press(Message::KeyboardLetterPressed(letter.clone()))
+                                    .into()
+                            })
+                            .collect(),
+                    )
+                    .spacing(5)
+                    .into()
+                })
+                .collect(),
+        )
+        .spacing(5);
+
+        let letter_choices = std::iter::once(KeyboardLetters::Alphabet)
+            .chain(
+                presets::PRESETS
+                    .iter()
+                    .map(|preset| KeyboardLetters::Preset(PresetChoice(preset))),
+            )
+            .collect_vec();
+        let mut keyboard_view = column![self.controls(vec![
+            text("Type into").into(),
+            pick_list(
+                &KeyboardTarget::ALL[..],
+                Some(self.keyboard_target),
+                Message::KeyboardTargetSelected
+            )
+            .into(),
+            text("Letters").into(),
+            pick_list(
+                letter_choices,
+                Some(self.keyboard_letters),
+                Message::KeyboardLettersSelected
+            )
+            .into(),
+            button("Space")
+                .on_press(Message::KeyboardLetterPressed(" ".to_owned()))
+                .into(),
+            button("Backspace")
+                .on_press(Message::KeyboardBackspacePressed)
+                .into(),
+        ])]
+        .spacing(10);
+        if letters.is_empty() {
+            keyboard_view = keyboard_view.push(text("The alphabet has no letters yet."));
+        }
+        keyboard_view.push(keys).into()
+    }
+
+    fn keyboard_input(&mut self) -> &mut String {
+        match self.keyboard_target {
+            KeyboardTarget::ReverseTranslation => &mut self.reverse_input,
+            KeyboardTarget::OwnTranslation => &mut self.custom_translation_input,
+        }
+    }
+
+    fn dictionary_tab(&self) -> Element<'_, Message> {
+        let translation_lookup = text_input(
+            "Which saved translation needs to be changed?",
+            &self.translation_lookup_input,
+        )
+        .on_input(Message::TranslationLookupChanged)
+        .on_submit(Message::EditTranslation)
+        .padding(15);
+
+        let mut translation_edit_view = column![].spacing(10);
+        if let Some(edit) = &self.translation_being_edited {
+            translation_edit_view = translation_edit_view
+                .push(
                     row![
-                        text(&value.0),
+                        text_input("Word", &edit.new_word)
+                            .on_input(Message::EditedWordChanged)
+                            .on_submit(Message::SaveTranslationEdit),
                         text("->"),
-                        text_input("", &value.1.translation)
-                            .on_input(Message::DummyToMakeTextInputSelectable),
-                        accept_button,
-                        reset_button,
-                        block_button
+                        text_input("Translation", &edit.new_translation)
+                            .on_input(Message::EditedTranslationChanged)
+                            .on_submit(Message::SaveTranslationEdit)
+                    ]
+                    .spacing(10),
+                )
+                .push(checkbox(
+                    format!(
+                        "Add the old translation \"{}\" to the block list",
+                        edit.translation
+                    ),
+                    edit.block_old,
+                    Message::BlockOldTranslationToggled,
+                ))
+                .push(
+                    row![
+                        button("Save changes").on_press(Message::SaveTranslationEdit),
+                        button("Delete translation").on_press(Message::DeleteTranslation),
+                        button("Cancel").on_press(Message::CancelTranslationEdit)
                     ]
                     .spacing(10),
                 );
-            }
+        }
+        if let Some(error) = &self.translation_edit_error {
+            translation_edit_view =
+                translation_edit_view.push(text(error).style(Color::from([0.8, 0.0, 0.0])));
         }
 
-        let lookup_feature =
-            text("For looking up existing words, please search the file in the data directory.");
+        let dictionary_controls = self.controls(vec![
+            text_input("Search the dictionary", &self.dictionary_query.search)
+                .on_input(Message::DictionarySearchChanged)
+                .into(),
+            pick_list(
+                &SearchDirection::ALL[..],
+                Some(self.dictionary_query.direction),
+                Message::DictionaryDirectionSelected,
+            )
+            .into(),
+            pick_list(
+                &DictionarySort::ALL[..],
+                Some(self.dictionary_query.sort),
+                Message::DictionarySortSelected,
+            )
+            .into(),
+            checkbox(
+                "Descending",
+                self.dictionary_query.descending,
+                Message::DictionaryDescendingToggled,
+            )
+            .into(),
+        ]);
 
-        let proper_unicode_support = text("Iced does not properly support Unicode.
-It will accept Latin and Greek in both input fields and text fields. It will accept Elder Futhark in input fields, but won't render it in text fields.
-It crashes on Hebrew.
-So, until either Iced implement proper Unicode support or I get around to replacing Iced with a web UI, all text fields that contain letters from the alphabet are input fields. This hard to read and even worse to copy pasted. But it's something at least...
-And Hebrew is not supported.");
+        let mut dictionary_view = column![].spacing(5);
+        for (word, translation) in self.dictionary_page.entries.iter() {
+            dictionary_view = dictionary_view.push(
+                row![
+                    text(word),
+                    text("->"),
+                    scrambled_text(&translation.translation),
+                    button("Edit").on_press(Message::OpenTranslationEdit(word.clone()))
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center),
+            );
+        }
+
+        let page = self.dictionary_page.page;
+        let mut previous_page_button = button("Previous");
+        if page > 0 {
+            previous_page_button =
+                previous_page_button.on_press(Message::DictionaryPageSelected(page - 1));
+        }
+        let mut next_page_button = button("Next");
+        if page + 1 < self.dictionary_page.page_count {
+            next_page_button = next_page_button.on_press(Message::DictionaryPageSelected(page + 1));
+        }
+        let dictionary_paging = row![
+            previous_page_button,
+            text(format!(
+                "Page {} of {} ({} words)",
+                page + 1,
+                self.dictionary_page.page_count,
+                self.dictionary_page.total_matches
+            )),
+            next_page_button
+        ]
+        .spacing(10)
+        .align_items(alignment::Alignment::Center);
+
+        column![
+            translation_lookup,
+            translation_edit_view,
+            dictionary_controls,
+            dictionary_view,
+            dictionary_paging
+        ]
+        .spacing(20)
+        .into()
+    }
 
+    fn alphabet_tab(&self) -> Element<'_, Message> {
         let alphabet_input = text_input(
             "Which letter needs to be added to the alphabet?",
             &self.alphabet_input,
@@ -182,82 +1724,740 @@ And Hebrew is not supported.");
         .on_submit(Message::AddToAlphabet)
         .padding(15);
 
-        let remove_alphabet_feature = text("For removing a character from the alphabet, please remove it from the file in the data directory.");
+        let presets = presets::PRESETS.iter().map(PresetChoice).collect_vec();
+        let mut preset_view = column![
+            text("Import letters from a built-in alphabet:"),
+            pick_list(presets, self.selected_preset, Message::PresetSelected)
+                .placeholder("Choose an alphabet")
+        ]
+        .spacing(10);
+        if let Some(preset) = self.selected_preset {
+            for group in preset.0.groups() {
+                let letter_count = preset
+                    .0
+                    .letters
+                    .iter()
+                    .filter(|letter| letter.group == group)
+                    .count();
+                preset_view = preset_view.push(checkbox(
+                    format!("{group} ({letter_count} letters)"),
+                    self.selected_preset_groups.contains(&group),
+                    move |checked| Message::PresetGroupToggled(group, checked),
+                ));
+            }
+            preset_view = preset_view.push(
+                text_input(
+                    "Or only these letters, separated by spaces",
+                    &self.preset_letters_input,
+                )
+                .on_input(Message::PresetLettersInputChanged)
+                .on_submit(Message::ImportPreset),
+            );
+            preset_view = preset_view.push(
+                button("Import (everything if nothing is selected)")
+                    .on_press(Message::ImportPreset),
+            );
+        }
+
+        let alphabet_error = match &self.alphabet_error {
+            Some(error) => text(error).style(Color::from([0.8, 0.0, 0.0])),
+            None => text(""),
+        };
 
         let alphabet_text = text("The current alphabet is: ");
 
-        let alphabet_value = text_input(
-            "",
-            &self
-                .current_alphabet
-                .iter()
-                .map(|glyph| &glyph.symbol)
-                .join(""),
-        )
-        .on_input(Message::DummyToMakeTextInputSelectable);
+        let mut alphabet_editor = column![].spacing(5);
+        for (index, glyph) in self.current_alphabet.iter().enumerate() {
+            let symbol = &glyph.symbol;
+            let mut glyph_row = row![].spacing(10);
+            match &self.glyph_being_edited {
+                Some((edited, replacement)) if edited == symbol => {
+                    glyph_row = glyph_row
+                        .push(
+                            text_input("Replacement letter", replacement)
+                                .on_input(Message::GlyphReplacementChanged)
+                                .on_submit(Message::ReplaceGlyph)
+                                .width(120),
+                        )
+                        .push(button("Save").on_press(Message::ReplaceGlyph))
+                        .push(button("Cancel").on_press(Message::CancelEditGlyph));
+                }
+                _ => {
+                    let mut up_button = button("Up");
+                    if index > 0 {
+                        up_button =
+                            up_button.on_press(Message::MoveGlyph(symbol.clone(), index - 1));
+                    }
+                    let mut down_button = button("Down");
+                    if index + 1 < self.current_alphabet.len() {
+                        down_button =
+                            down_button.on_press(Message::MoveGlyph(symbol.clone(), index + 1));
+                    }
+                    glyph_row = glyph_row
+                        .push(scrambled_text(symbol).width(120))
+                        .push(up_button)
+                        .push(down_button)
+                        .push(button("Replace").on_press(Message::EditGlyph(symbol.clone())))
+                        .push(button("Delete").on_press(Message::RemoveGlyph(symbol.clone())));
+                }
+            }
+            alphabet_editor = alphabet_editor.push(glyph_row);
+        }
 
-        let translation_column = column![
-            input,
-            translation,
-            suggested_translations_view,
-            lookup_feature,
-            proper_unicode_support
-        ]
-        .spacing(20)
-        .max_width(1200);
+        let removal_warning = match &self.pending_glyph_removal {
+            Some((symbol, words)) => column![
+                text(format!(
+                    "The letter \"{symbol}\" is used by the translations of: {}. These translations are kept as they are. Remove the letter anyway?",
+                    words.join(", ")
+                ))
+                .style(Color::from([0.8, 0.4, 0.0])),
+                row![
+                    button("Remove anyway").on_press(Message::ConfirmRemoveGlyph),
+                    button("Keep the letter").on_press(Message::CancelRemoveGlyph)
+                ]
+                .spacing(10)
+            ]
+            .spacing(10),
+            None => column![],
+        };
 
-        let alphabet_column = column![
+        column![
             alphabet_input,
-            remove_alphabet_feature,
+            preset_view,
+            alphabet_error,
             alphabet_text,
-            alphabet_value
+            removal_warning,
+            alphabet_editor
         ]
         .spacing(20)
-        .max_width(600);
+        .into()
+    }
 
-        let body = row![translation_column, alphabet_column];
+    fn block_list_tab(&self) -> Element<'_, Message> {
+        let block_list_input =
+            text_input("Which word needs to be blocked?", &self.block_list_input)
+                .on_input(Message::BlockListInputChanged)
+                .on_submit(Message::AddToBlockList)
+                .padding(15);
 
-        let content = column![title, body].spacing(20).max_width(1800);
+        let block_reason_input =
+            text_input("Why is it blocked? (optional)", &self.block_reason_input)
+                .on_input(Message::BlockReasonInputChanged)
+                .on_submit(Message::AddToBlockList)
+                .padding(15);
 
-        scrollable(
-            container(content)
-                .width(Length::Fill)
-                .padding(40)
-                .center_x(),
+        let block_pattern_input = text_input(
+            "Block every word containing or matching...",
+            &self.block_pattern_input,
         )
+        .on_input(Message::BlockPatternInputChanged)
+        .padding(15);
+        let block_pattern_buttons = row![
+            button("Block substring").on_press(Message::AddBlockPattern(BlockPattern::Substring)),
+            button("Block regex").on_press(Message::AddBlockPattern(BlockPattern::Regex))
+        ]
+        .spacing(10);
+
+        let block_list_error = match &self.block_list_error {
+            Some(error) => text(error).style(Color::from([0.8, 0.0, 0.0])),
+            None => text(""),
+        };
+
+        let mut block_patterns_view = column![text("Blocked patterns:")].spacing(5);
+        for pattern in self.block_patterns.iter() {
+            block_patterns_view = block_patterns_view.push(
+                row![
+                    scrambled_text(pattern.to_string()),
+                    button("Remove").on_press(Message::RemoveBlockPattern(pattern.clone()))
+                ]
+                .spacing(10),
+            );
+        }
+
+        let block_list_search = text_input("Search the block list", &self.block_list_search)
+            .on_input(Message::BlockListSearchChanged)
+            .padding(15);
+
+        let mut block_list_view = column![text("Blocked words:")].spacing(5);
+        for (word, blocked) in self.block_list.iter() {
+            let mut details = match &blocked.reason {
+                Some(reason) => reason.clone(),
+                None => "No reason given".to_owned(),
+            };
+            if blocked.time_added != SystemTime::UNIX_EPOCH {
+                let time_added: chrono::DateTime<chrono::Local> = blocked.time_added.into();
+                details += &time_added.format(", blocked on %Y-%m-%d").to_string();
+            }
+            block_list_view = block_list_view.push(
+                row![
+                    scrambled_text(word),
+                    text(details),
+                    button("Unblock").on_press(Message::Unblock(word.clone()))
+                ]
+                .spacing(10),
+            );
+        }
+
+        column![
+            block_list_input,
+            block_reason_input,
+            block_pattern_input,
+            block_pattern_buttons,
+            block_list_error,
+            block_patterns_view,
+            block_list_search,
+            block_list_view
+        ]
+        .spacing(20)
         .into()
     }
-}
 
-impl ScramblerUi {
-    fn translate_input(&mut self) {
-        self.translated_value = None;
-        self.suggested_translations = Vec::new();
-
-        let mut translations = vec![];
-        let mut suggested_translations = vec![];
-
-        for word in self.input_value.split_whitespace() {
-            match scrambler::translate_word(&word) {
-                Ok(translation) => match scrambler::is_word_known(&word) {
-                    true => translations.push(translation),
-                    false => suggested_translations.push((word.to_owned(), translation)),
-                },
-                Err(error) => {
-                    error!("{error}");
-                }
+    fn players_tab(&self) -> Element<'_, Message> {
+        let party_input = self.controls(vec![
+            text_input("Name of the party", &self.party_input)
+                .on_input(Message::PartyInputChanged)
+                .on_submit(Message::OpenParty)
+                .padding(15)
+                .into(),
+            button("Open party").on_press(Message::OpenParty).into(),
+            pick_list(
+                &self.parties[..],
+                self.current_party.clone(),
+                Message::PartySelected,
+            )
+            .placeholder("Choose a party")
+            .into(),
+        ]);
+
+        let players_error = match &self.players_error {
+            Some(error) => text(error).style(Color::from([0.8, 0.0, 0.0])),
+            None => text(""),
+        };
+
+        let Some(party) = &self.current_party else {
+            return column![
+                party_input,
+                players_error,
+                text("Open a party to see which words they know. A new party is saved once a word is revealed to them.")
+            ]
+            .spacing(20)
+            .into();
+        };
+
+        let reveal_input = self.controls(vec![
+            text_input("Which words did the party learn?", &self.reveal_input)
+                .on_input(Message::RevealInputChanged)
+                .on_submit(Message::RevealWords)
+                .padding(15)
+                .into(),
+            text_input("Session date, like 2024-03-01", &self.session_input)
+                .on_input(Message::SessionInputChanged)
+                .on_submit(Message::RevealWords)
+                .padding(15)
+                .width(250)
+                .into(),
+            button("Reveal").on_press(Message::RevealWords).into(),
+        ]);
+
+        let mut decipher_view = column![self.controls(vec![
+            text_input(
+                "English text to show as the party would read it",
+                &self.decipher_input,
+            )
+            .on_input(Message::DecipherInputChanged)
+            .on_submit(Message::Decipher)
+            .padding(15)
+            .into(),
+            pick_list(
+                &RevealedStyle::ALL[..],
+                Some(self.revealed_style),
+                Message::RevealedStyleSelected,
+            )
+            .into(),
+            button("Render").on_press(Message::Decipher).into(),
+        ])]
+        .spacing(10);
+        if let Some(words) = &self.deciphered {
+            let rendered = knowledge::render(words, self.revealed_style);
+            decipher_view = decipher_view.push(
+                row![
+                    scrambled_text(&rendered),
+                    button("Copy").on_press(Message::CopyToClipboard(rendered))
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center),
+            );
+        }
+
+        let vocabulary_search = text_input("Search the vocabulary", &self.vocabulary_search)
+            .on_input(Message::VocabularySearchChanged)
+            .padding(15);
+
+        let search = self.vocabulary_search.to_lowercase();
+        let (known, unknown): (Vec<_>, Vec<_>) = self
+            .vocabulary
+            .iter()
+            .filter(|entry| {
+                entry.word.to_lowercase().contains(&search)
+                    || entry.translation.to_lowercase().contains(&search)
+            })
+            .partition(|entry| entry.revealed.is_some());
+
+        let mut known_view =
+            column![text(format!("{party} knows {} words:", known.len()))].spacing(5);
+        for entry in known.iter().take(MAX_VOCABULARY_LENGTH) {
+            let Some(revealed) = &entry.revealed else {
+                continue;
+            };
+            let mut details = format!("revealed on {}", revealed.session);
+            if entry.is_outdated() {
+                details += &format!(", but as {}", revealed.translation);
             }
+            known_view = known_view.push(
+                row![
+                    text(&entry.word),
+                    scrambled_text(&entry.translation),
+                    scrambled_text(details),
+                    button("Hide").on_press(Message::HideWord(entry.word.clone()))
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center),
+            );
         }
 
-        if suggested_translations.is_empty() {
-            self.translated_value = Some(
-                translations
-                    .iter()
-                    .map(|translation| &translation.translation)
-                    .join(" "),
+        let mut unknown_view = column![text(format!(
+            "{party} doesn't know {} words:",
+            unknown.len()
+        ))]
+        .spacing(5);
+        for entry in unknown.iter().take(MAX_VOCABULARY_LENGTH) {
+            unknown_view = unknown_view.push(
+                row![
+                    text(&entry.word),
+                    scrambled_text(&entry.translation),
+                    button("Reveal").on_press(Message::RevealWord(entry.word.clone()))
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center),
             );
-        } else {
-            self.suggested_translations = suggested_translations;
         }
+        if known.len() > MAX_VOCABULARY_LENGTH {
+            known_view = known_view.push(text(unlisted_words_hint(known.len())));
+        }
+        if unknown.len() > MAX_VOCABULARY_LENGTH {
+            unknown_view = unknown_view.push(text(unlisted_words_hint(unknown.len())));
+        }
+
+        column![
+            party_input,
+            reveal_input,
+            decipher_view,
+            players_error,
+            vocabulary_search,
+            self.controls(vec![known_view.into(), unknown_view.into()])
+        ]
+        .spacing(20)
+        .into()
+    }
+
+    /// Moves the warnings and errors logged since the last update into the notification area.
+    fn collect_notifications(&mut self) {
+        let logged = std::mem::take(
+            &mut *LOGGED_NOTIFICATIONS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        self.notifications.extend(logged);
+        let excess = self.notifications.len().saturating_sub(MAX_NOTIFICATIONS);
+        self.notifications.drain(..excess);
+    }
+
+    fn notifications_view(&self) -> Element<'_, Message> {
+        let mut notifications_view = Column::new().spacing(5);
+        for (index, notification) in self.notifications.iter().enumerate() {
+            let color = match notification.level {
+                Level::Error => Color::from([0.8, 0.0, 0.0]),
+                _ => Color::from([0.8, 0.4, 0.0]),
+            };
+            let details_label = match notification.show_details {
+                true => "Hide details",
+                false => "Show details",
+            };
+            notifications_view = notifications_view.push(
+                row![
+                    text(notification.level).style(color),
+                    text(notification.time.format("%H:%M:%S")),
+                    scrambled_text(notification.summary()).width(Length::Fill),
+                    button(details_label).on_press(Message::NotificationDetailsToggled(index)),
+                    button("Dismiss").on_press(Message::DismissNotification(index)),
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::Center),
+            );
+            if notification.show_details {
+                notifications_view =
+                    notifications_view.push(scrambled_text(notification.details()));
+            }
+        }
+
+        let header = row![
+            text(format!("{} notification(s)", self.notifications.len())).width(Length::Fill),
+            button("Dismiss all").on_press(Message::DismissAllNotifications),
+        ]
+        .spacing(10)
+        .align_items(alignment::Alignment::Center);
+
+        // A few notifications are shown as they are, more of them get a scroll bar.
+        let height = match self.notifications.len() {
+            0..=3 => Length::Shrink,
+            _ => Length::Fixed(150.0),
+        };
+        column![header, scrollable(notifications_view).height(height)]
+            .spacing(5)
+            .into()
+    }
+
+    fn open_party(&mut self, party: String) -> Command<Message> {
+        self.players_error = None;
+        if party.is_empty() {
+            self.players_error = Some("A party needs a name.".to_owned());
+            return Command::none();
+        }
+        self.current_party = Some(party);
+        self.vocabulary = Vec::new();
+        self.deciphered = None;
+        self.load_party()
+    }
+
+    fn load_party(&mut self) -> Command<Message> {
+        let party = self.current_party.clone();
+        self.background(
+            move || {
+                let vocabulary = party
+                    .as_deref()
+                    .map(scrambler::party_vocabulary)
+                    .unwrap_or_default();
+                (party, scrambler::parties(), vocabulary)
+            },
+            |(party, parties, vocabulary)| Message::PartyLoaded(party, parties, vocabulary),
+        )
+    }
+
+    fn reveal_words(
+        &mut self,
+        words: Vec<String>,
+        done: impl FnOnce(Result<(), String>) -> Message + Send + 'static,
+    ) -> Command<Message> {
+        self.players_error = None;
+        let Some(party) = self.current_party.clone() else {
+            return Command::none();
+        };
+        let session =
+            match NaiveDate::parse_from_str(self.session_input.trim(), SESSION_DATE_FORMAT) {
+                Ok(session) => session,
+                Err(_) => {
+                    self.players_error =
+                        Some("The session date must look like 2024-03-01.".to_owned());
+                    return Command::none();
+                }
+            };
+        self.background(
+            move || {
+                scrambler::reveal_words(&party, &words, session)
+                    .map(|_| ())
+                    .map_err(|error| error.to_string())
+            },
+            done,
+        )
+    }
+
+    /// Runs storage work on a background thread, so the window doesn't freeze while files are read or written.
+    /// The tasks run one at a time, in the order they were started, so every task sees the changes
+    /// of the tasks before it.
+    fn background<T: Send + 'static>(
+        &mut self,
+        work: impl FnOnce() -> T + Send + 'static,
+        done: impl FnOnce(T) -> Message + Send + 'static,
+    ) -> Command<Message> {
+        self.pending_tasks += 1;
+        let (sender, receiver) = oneshot::channel();
+        storage_queue()
+            .send(Box::new(move || {
+                let _ = sender.send(work());
+            }))
+            .expect("The storage thread runs as long as the application");
+        Command::perform(receiver, move |result| match result {
+            Ok(result) => Message::TaskFinished(Box::new(done(result))),
+            Err(oneshot::Canceled) => Message::TaskFailed,
+        })
+    }
+
+    fn change_alphabet(
+        &mut self,
+        change: impl FnOnce() -> Result<(), Box<dyn Error>> + Send + 'static,
+    ) -> Command<Message> {
+        self.background(
+            || change().map_err(|error| error.to_string()),
+            Message::AlphabetChanged,
+        )
+    }
+
+    fn change_block_list(
+        &mut self,
+        change: impl FnOnce() -> Result<(), Box<dyn Error>> + Send + 'static,
+    ) -> Command<Message> {
+        self.background(
+            || change().map_err(|error| error.to_string()),
+            Message::BlockListChanged,
+        )
+    }
+
+    fn load_alphabet(&mut self) -> Command<Message> {
+        self.background(scrambler::storage::load_alphabet, Message::AlphabetLoaded)
+    }
+
+    /// Loads the blocked words that match the search, and all block patterns.
+    fn load_block_list(&mut self) -> Command<Message> {
+        let search = self.block_list_search.clone();
+        self.background(
+            move || {
+                (
+                    scrambler::search_block_list(&search),
+                    scrambler::block_patterns(),
+                )
+            },
+            Message::BlockListLoaded,
+        )
+    }
+
+    fn apply_block_list_change(&mut self, result: Result<(), String>) -> Command<Message> {
+        self.block_list_error = None;
+        if let Err(error) = result {
+            error!("{error}");
+            self.block_list_error = Some(error);
+        }
+        self.load_block_list()
+    }
+
+    /// Shows what an undo or redo did, and reloads everything it may have changed.
+    fn apply_journal_step(
+        &mut self,
+        result: Result<Option<String>, String>,
+        done: &str,
+        nothing_to_do: &str,
+    ) -> Command<Message> {
+        self.journal_message = Some(match result {
+            Ok(Some(description)) => format!("{done}: {description}"),
+            Ok(None) => nothing_to_do.to_owned(),
+            Err(error) => {
+                error!("{error}");
+                error
+            }
+        });
+
+        let mut commands = vec![
+            self.load_alphabet(),
+            self.load_block_list(),
+            self.refresh_dictionary(),
+        ];
+        // A finished translation may use a word whose translation was just undone.
+        if self.translated_value.is_some() {
+            commands.push(self.translate_input());
+        }
+        Command::batch(commands)
+    }
+
+    fn apply_alphabet_change(&mut self, result: Result<(), String>) -> Command<Message> {
+        self.alphabet_error = None;
+        if let Err(error) = result {
+            error!("{error}");
+            self.alphabet_error = Some(error);
+        }
+        self.load_alphabet()
+    }
+
+    fn refresh_dictionary(&mut self) -> Command<Message> {
+        self.background(
+            scrambler::storage::load_translated_words,
+            Message::DictionaryLoaded,
+        )
+    }
+
+    fn query_dictionary(&mut self) {
+        self.dictionary_page = dictionary::query(&self.dictionary, &self.dictionary_query);
+        self.dictionary_query.page = self.dictionary_page.page;
+    }
+
+    fn open_translation_edit(&mut self, word: &str) {
+        self.translation_edit_error = None;
+        self.translation_being_edited = None;
+        match self.dictionary.get(word) {
+            Some(translation) => {
+                self.translation_being_edited = Some(TranslationEdit {
+                    word: word.to_owned(),
+                    translation: translation.translation.clone(),
+                    new_word: word.to_owned(),
+                    new_translation: translation.translation.clone(),
+                    block_old: false,
+                })
+            }
+            None => {
+                self.translation_edit_error =
+                    Some("The word \"".to_owned() + word + "\" has no translation yet.")
+            }
+        }
+    }
+
+    fn find_suggestion(&self, word: &str) -> Option<&Suggestion> {
+        self.suggested_translations
+            .iter()
+            .find(|suggestion| suggestion.word == word)
+    }
+
+    fn find_suggestion_mut(&mut self, word: &str) -> Option<&mut Suggestion> {
+        self.suggested_translations
+            .iter_mut()
+            .find(|suggestion| suggestion.word == word)
+    }
+
+    /// Saves suggestions. The saved ones are no longer suggestions.
+    fn save_suggestions(&mut self, words: Vec<String>) -> Command<Message> {
+        let suggestions = words
+            .iter()
+            .filter_map(|word| self.find_suggestion(word))
+            .map(|suggestion| (suggestion.word.clone(), suggestion.translation.clone()))
+            .collect_vec();
+        self.background(
+            move || {
+                let mut results = vec![];
+                for (word, mut translation) in suggestions {
+                    // Two suggestions can be the same, or another word took it in the meantime.
+                    if let Some(other) = scrambler::reverse_translate_word(&translation.translation)
+                        .filter(|other| *other != word)
+                    {
+                        match scrambler::translate_word(&word) {
+                            Ok(new_translation) => {
+                                warn!(
+                                    "\"{}\" is already used for \"{other}\", so \"{word}\" is translated as \"{}\" instead.",
+                                    translation.translation, new_translation.translation
+                                );
+                                translation = new_translation;
+                            }
+                            Err(error) => {
+                                results.push((word, Err(SaveError::Failed(error.to_string()))));
+                                continue;
+                            }
+                        }
+                    }
+                    let result = scrambler::save_translation(&word, translation, false)
+                        .map_err(SaveError::from);
+                    let conflict = matches!(result, Err(SaveError::Conflict(..)));
+                    results.push((word, result));
+                    // Only a single conflict can be shown, the other words stay suggestions.
+                    if conflict {
+                        break;
+                    }
+                }
+                results
+            },
+            Message::SuggestionsSaved,
+        )
+    }
+
+    /// Shows the translated sentence once all suggestions are handled.
+    fn finish_suggestions(&mut self) -> Command<Message> {
+        let mut commands = vec![self.refresh_dictionary()];
+        if self.suggested_translations.is_empty() {
+            commands.push(self.translate_input());
+        }
+        Command::batch(commands)
+    }
+
+    fn regenerate_suggestion(&mut self, word: String) -> Command<Message> {
+        self.background(
+            move || {
+                let translation =
+                    scrambler::translate_word(&word).map_err(|error| error.to_string());
+                (word, translation)
+            },
+            |(word, translation)| Message::SuggestionRegenerated(word, translation),
+        )
+    }
+
+    fn apply_regenerated_suggestion(&mut self, word: &str, result: Result<Translation, String>) {
+        match result {
+            Ok(translation) => {
+                if let Some(suggestion) = self.find_suggestion_mut(word) {
+                    suggestion.translation = translation;
+                    suggestion.candidates = Vec::new();
+                }
+            }
+            Err(error) => {
+                error!("{error}");
+                self.translation_errors.push(error);
+            }
+        }
+    }
+
+    fn translate_input(&mut self) -> Command<Message> {
+        let sentence = self.input_value.clone();
+        self.background(
+            move || translate_sentence(&sentence),
+            Message::SentenceTranslated,
+        )
+    }
+}
+
+/// Tells how many words of a long vocabulary list are left out.
+fn unlisted_words_hint(count: usize) -> String {
+    format!(
+        "And {} more. Search to find them.",
+        count - MAX_VOCABULARY_LENGTH
+    )
+}
+
+/// Translates scrambled text back to English. Unknown words are shown between brackets.
+fn reverse_translate_sentence(scrambled: &str) -> String {
+    scrambled
+        .split_whitespace()
+        .map(|word| match scrambler::reverse_translate_word(word) {
+            Some(original) => original,
+            None => format!("[{word}]"),
+        })
+        .join(" ")
+}
+
+/// Translates every word of the sentence. Words without translation become suggestions.
+fn translate_sentence(sentence: &str) -> TranslatedSentence {
+    let mut translations = vec![];
+    let mut suggestions: Vec<Suggestion> = vec![];
+    let mut errors = vec![];
+
+    for word in sentence.split_whitespace() {
+        let word = scrambler::strip_punctuation(word);
+        if word.is_empty() {
+            continue;
+        }
+        match scrambler::translate_word(&word) {
+            Ok(translation) => match scrambler::is_word_known(&word) {
+                true => translations.push(translation),
+                // A word that appears twice in the sentence gets a single suggestion.
+                false if suggestions.iter().any(|suggestion| suggestion.word == word) => {}
+                false => suggestions.push(Suggestion {
+                    word,
+                    translation,
+                    locked: false,
+                    candidates: Vec::new(),
+                }),
+            },
+            Err(error) => errors.push(error.to_string()),
+        }
+    }
+
+    TranslatedSentence {
+        english: sentence.trim().to_owned(),
+        translations,
+        suggestions,
+        errors,
     }
 }
@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use itertools::Itertools;
 use regex::Regex;
 use serde::Deserialize;
@@ -6,12 +7,27 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::time::SystemTime;
+
+use block_list::BlockList;
+use block_list::BlockPattern;
+use block_list::BlockPatternMatcher;
+use knowledge::DecipheredWord;
+use knowledge::PartyKnowledge;
+use knowledge::VocabularyEntry;
+use rules::RuleViolation;
+use rules::WordRules;
 use unicode_segmentation::UnicodeSegmentation;
 
+pub mod block_list;
+pub mod dictionary;
 mod generator;
+pub mod journal;
+pub mod knowledge;
+pub mod presets;
+pub mod rules;
 pub mod storage;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Translation {
     pub translation: String,
     pub time_added: SystemTime,
@@ -26,7 +42,7 @@ impl Translation {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Glyph {
     pub symbol: String,
     pub time_added: SystemTime,
@@ -52,68 +68,799 @@ pub fn is_word_known(word: &str) -> bool {
 }
 
 pub fn translate_word(word: &str) -> Result<Translation, Box<dyn Error>> {
-    match word.split_whitespace().count() {
-        0 => Ok(Translation::new("".to_owned())),
-        1 => translate_word_impl(word),
-        _ => Err(ScramblerError(
-            "Error! I can only translate single words. The input \"".to_owned()
-                + word
-                + "\" is not a single word.",
+    if word.trim().is_empty() {
+        return Ok(Translation::new("".to_owned()));
+    }
+    check_single_word(word)?;
+    translate_word_impl(word)
+}
+
+/// Generates up to `count` different translations for a word, so the user can pick one.
+///
+/// None of them are saved. If the word already has a translation, only that translation is returned.
+pub fn translation_candidates(
+    word: &str,
+    count: usize,
+) -> Result<Vec<Translation>, Box<dyn Error>> {
+    check_single_word(word)?;
+
+    let word = strip_punctuation(word);
+    if word.trim().is_empty() {
+        return Err(ScramblerError(
+            "I cannot translate a string that consists of only whitespace!".to_owned(),
         )
-        .into()),
+        .into());
+    }
+    let known_translations = storage::load_translated_words();
+    if let Some(translation) = known_translations.get(&word) {
+        return Ok(vec![translation.clone()]);
+    }
+
+    let blocked_translations = storage::load_blocked_translations();
+    let block_patterns = BlockPatternMatcher::new(&storage::load_block_patterns());
+    generator::new_translations(&word, count, &|candidate| {
+        translation_is_rejected(candidate, &blocked_translations, &known_translations)
+            || block_patterns.matches(candidate)
+    })
+}
+
+/// Stores a translation chosen by the user instead of a generated one.
+///
+/// The translation must follow the same rules as a generated one. If it doesn't, an
+/// [`InvalidTranslationError`] tells exactly which rule it breaks.
+/// If the word already has a different translation, a [`TranslationConflictError`] is returned,
+/// unless `overwrite` is `true`.
+pub fn assign_translation(
+    word: &str,
+    translation: &str,
+    overwrite: bool,
+) -> Result<Translation, Box<dyn Error>> {
+    let word = strip_punctuation(word);
+    check_single_word(&word)?;
+
+    let mut known_translations = storage::load_translated_words();
+    validate_translation(
+        &word,
+        translation,
+        &storage::load_alphabet(),
+        &storage::load_word_rules(),
+        &storage::load_blocked_translations(),
+        &BlockPatternMatcher::new(&storage::load_block_patterns()),
+        &known_translations,
+    )?;
+
+    let translation = Translation::new(translation.to_owned());
+    let before = known_translations.clone();
+    if insert_translation(
+        &mut known_translations,
+        &word,
+        translation.clone(),
+        overwrite,
+    )? {
+        save_translations_change(
+            format!("Translate \"{word}\" as \"{}\"", translation.translation),
+            &before,
+            &known_translations,
+        )?;
+    }
+    Ok(translation)
+}
+
+/// Stores the translation of a word.
+///
+/// If the word already has a different translation, a [`TranslationConflictError`] is returned,
+/// unless `overwrite` is `true`. A translation that is already used for another word is refused.
+pub fn save_translation(
+    word: &str,
+    translation: Translation,
+    overwrite: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut known_translations = storage::load_translated_words();
+    let before = known_translations.clone();
+    let description = format!("Translate \"{word}\" as \"{}\"", translation.translation);
+    if insert_translation(&mut known_translations, word, translation, overwrite)? {
+        save_translations_change(description, &before, &known_translations)?;
+    }
+    Ok(())
+}
+
+/// Returns the English word that was translated to `scrambled_word`, if any. Punctuation is ignored.
+pub fn reverse_translate_word(scrambled_word: &str) -> Option<String> {
+    let known_translations = storage::load_translated_words();
+    find_original_word(&strip_punctuation(scrambled_word), &known_translations).cloned()
+}
+
+/// Adds many translations at once, e.g. from an exported dictionary.
+///
+/// If one of the words already has a different translation, nothing is imported and a
+/// [`TranslationConflictError`] is returned, unless `overwrite` is `true`.
+/// Punctuation is removed from the English words, like when translating them.
+/// Nothing is imported either if a word is not a single word, or if a translation is blocked,
+/// uses letters outside the alphabet, or is used by another word, including another word of the
+/// same import.
+/// Returns the number of words that were added or changed.
+pub fn import_translations(
+    translations: HashMap<String, Translation>,
+    overwrite: bool,
+) -> Result<usize, Box<dyn Error>> {
+    let mut known_translations = storage::load_translated_words();
+    let before = known_translations.clone();
+    let changed = merge_translations(
+        &mut known_translations,
+        translations,
+        &storage::load_alphabet(),
+        &storage::load_blocked_translations(),
+        &BlockPatternMatcher::new(&storage::load_block_patterns()),
+        overwrite,
+    )?;
+    if changed > 0 {
+        save_translations_change(
+            format!("Import {changed} translations"),
+            &before,
+            &known_translations,
+        )?;
     }
+    Ok(changed)
 }
 
-pub fn save_translation(word: &str, translation: Translation) -> Result<(), Box<dyn Error>> {
+/// Changes the English word of an existing translation. The translation itself is kept.
+/// Returns the word as it was stored, without punctuation.
+pub fn rename_translation(old_word: &str, new_word: &str) -> Result<String, Box<dyn Error>> {
     let mut known_translations = storage::load_translated_words();
-    known_translations.insert(word.to_owned(), translation);
+    let before = known_translations.clone();
+    let new_word = rename_entry(&mut known_translations, old_word, new_word)?;
 
-    storage::save_translated_words(&known_translations)
+    save_translations_change(
+        format!("Rename \"{old_word}\" to \"{new_word}\""),
+        &before,
+        &known_translations,
+    )?;
+    Ok(new_word)
 }
 
-/// Adds a character to the alphabet used to generate new words
+/// Replaces the translation of an existing word by `new_translation`.
+///
+/// The new translation must not be blocked or be the translation of another word.
+/// If `block_old` is `true`, the old translation is added to the block list.
+/// Returns the new translation.
+pub fn replace_translation(
+    word: &str,
+    new_translation: &str,
+    block_old: bool,
+) -> Result<Translation, Box<dyn Error>> {
+    let mut known_translations = storage::load_translated_words();
+    let before = known_translations.clone();
+    let replaced = replace_entry(
+        &mut known_translations,
+        word,
+        new_translation,
+        &storage::load_blocked_translations(),
+        &BlockPatternMatcher::new(&storage::load_block_patterns()),
+    )?;
+    let translation = known_translations[word].clone();
+
+    if let Some(old_translation) = replaced {
+        let reason = format!("Replaced as translation of \"{word}\"");
+        save_edit(
+            format!(
+                "Change the translation of \"{word}\" to \"{}\"",
+                translation.translation
+            ),
+            &before,
+            &known_translations,
+            block_old.then_some((&old_translation.translation, reason)),
+        )?;
+    }
+    Ok(translation)
+}
+
+/// Replaces the translation of a word and renames the word at once, as a single change in the journal.
+///
+/// Nothing is saved if one of the two is not possible.
+/// If `block_old` is `true` and the translation changed, the old translation is added to the block list.
+/// Returns the word as it was stored, and its translation.
+pub fn edit_translation(
+    word: &str,
+    new_word: &str,
+    new_translation: &str,
+    block_old: bool,
+) -> Result<(String, Translation), Box<dyn Error>> {
+    let mut known_translations = storage::load_translated_words();
+    let before = known_translations.clone();
+    let replaced = replace_entry(
+        &mut known_translations,
+        word,
+        new_translation,
+        &storage::load_blocked_translations(),
+        &BlockPatternMatcher::new(&storage::load_block_patterns()),
+    )?;
+    let new_word = rename_entry(&mut known_translations, word, new_word)?;
+    let translation = known_translations[&new_word].clone();
+
+    let description = match (&replaced, new_word != word) {
+        (None, false) => return Ok((new_word, translation)),
+        (None, true) => format!("Rename \"{word}\" to \"{new_word}\""),
+        (Some(_), false) => format!(
+            "Change the translation of \"{word}\" to \"{}\"",
+            translation.translation
+        ),
+        (Some(_), true) => format!(
+            "Rename \"{word}\" to \"{new_word}\" and change its translation to \"{}\"",
+            translation.translation
+        ),
+    };
+    let reason = format!("Replaced as translation of \"{word}\"");
+    save_edit(
+        description,
+        &before,
+        &known_translations,
+        replaced
+            .as_ref()
+            .filter(|_| block_old)
+            .map(|old_translation| (&old_translation.translation, reason)),
+    )?;
+    Ok((new_word, translation))
+}
+
+/// Removes a word and its translation.
+/// If `block_old` is `true`, the translation is added to the block list, so it is never generated again.
+pub fn delete_translation(word: &str, block_old: bool) -> Result<(), Box<dyn Error>> {
+    let mut known_translations = storage::load_translated_words();
+    let old_translation = find_translation(&known_translations, word)?.clone();
+    let before = known_translations.clone();
+    known_translations.remove(word);
+    let reason = format!("Deleted as translation of \"{word}\"");
+    save_edit(
+        format!("Delete \"{word}\""),
+        &before,
+        &known_translations,
+        block_old.then_some((&old_translation.translation, reason)),
+    )
+}
+
+/// Adds a letter to the alphabet used to generate new words
 ///
 /// # Arguments
 ///
-/// * `character` - A single character to be added to the alphabet.
-///                 A character is defined as a unicode grapheme cluster.
-///                 See http://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
-///                 The character must not be whitespace as adding whitespace to the alphabet
-///                 break the whole "translated words are of equivalent size" rule.
+/// * `character` - A single letter to be added to the alphabet.
+///   A letter usually is a single unicode grapheme cluster.
+///   See http://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
+///   It can also be a combination of multiple grapheme clusters, like the digraph "th".
+///   Such a combination counts as a single letter when generating words.
+///   The letter must not contain whitespace as adding whitespace to the alphabet
+///   break the whole "translated words are of equivalent size" rule.
 ///
-/// This function panics if the input does not contain a single non-whitespace character.
+/// Returns an error if the input is empty or contains whitespace.
 pub fn add_to_alphabet(character: &str) -> Result<(), Box<dyn Error>> {
-    let char_count = character.graphemes(true).count();
-    if char_count == 0 {
-        panic!("Expected a single character. Received no character.");
+    check_letter(character)?;
+
+    let mut current_alphabet = storage::load_alphabet();
+    let before = current_alphabet.clone();
+    add_glyphs(&mut current_alphabet, &[character]);
+
+    save_alphabet_change(
+        format!("Add the letter \"{character}\""),
+        &before,
+        &current_alphabet,
+    )
+}
+
+/// Removes a letter from the alphabet.
+///
+/// Existing translations are left untouched, even if they use the letter.
+/// Use [`translations_using_glyph`] to warn the user about those first.
+pub fn remove_from_alphabet(symbol: &str) -> Result<(), Box<dyn Error>> {
+    let mut current_alphabet = storage::load_alphabet();
+    let before = current_alphabet.clone();
+    let index = find_glyph(&current_alphabet, symbol)?;
+    current_alphabet.remove(index);
+
+    save_alphabet_change(
+        format!("Remove the letter \"{symbol}\""),
+        &before,
+        &current_alphabet,
+    )
+}
+
+/// Replaces a letter of the alphabet by another one, keeping its position.
+///
+/// Existing translations are left untouched, even if they use the old letter.
+pub fn replace_in_alphabet(old_symbol: &str, new_symbol: &str) -> Result<(), Box<dyn Error>> {
+    check_letter(new_symbol)?;
+
+    let mut current_alphabet = storage::load_alphabet();
+    let index = find_glyph(&current_alphabet, old_symbol)?;
+    if old_symbol != new_symbol && find_glyph(&current_alphabet, new_symbol).is_ok() {
+        return Err(ScramblerError(
+            "The letter \"".to_owned() + new_symbol + "\" is already in the alphabet.",
+        )
+        .into());
+    }
+    let before = current_alphabet.clone();
+    current_alphabet[index] = Glyph::new(new_symbol.to_owned());
+
+    save_alphabet_change(
+        format!("Replace the letter \"{old_symbol}\" by \"{new_symbol}\""),
+        &before,
+        &current_alphabet,
+    )
+}
+
+/// Moves a letter of the alphabet to `new_index`, shifting the letters in between.
+/// An index past the end of the alphabet moves the letter to the end.
+pub fn move_in_alphabet(symbol: &str, new_index: usize) -> Result<(), Box<dyn Error>> {
+    let mut current_alphabet = storage::load_alphabet();
+    let before = current_alphabet.clone();
+    let index = find_glyph(&current_alphabet, symbol)?;
+    let glyph = current_alphabet.remove(index);
+    current_alphabet.insert(new_index.min(current_alphabet.len()), glyph);
+
+    save_alphabet_change(
+        format!("Move the letter \"{symbol}\""),
+        &before,
+        &current_alphabet,
+    )
+}
+
+/// Returns all English words whose translation contains the letter, sorted alphabetically.
+pub fn translations_using_glyph(symbol: &str) -> Vec<String> {
+    let alphabet = storage::load_alphabet();
+    let known_translations = storage::load_translated_words();
+    words_using_glyph(symbol, &alphabet, &known_translations)
+}
+
+/// Adds letters from one of the built-in [`presets::PRESETS`] to the alphabet.
+///
+/// Letters that are already in the alphabet are skipped.
+/// Returns the amount of letters that were added.
+pub fn import_preset(
+    preset_id: &str,
+    selection: &presets::PresetSelection,
+) -> Result<usize, Box<dyn Error>> {
+    let preset = presets::find_preset(preset_id).ok_or_else(|| {
+        ScramblerError("There is no alphabet preset called \"".to_owned() + preset_id + "\".")
+    })?;
+    let letters = preset
+        .select(selection)?
+        .iter()
+        .map(|letter| letter.symbol)
+        .collect_vec();
+
+    let mut current_alphabet = storage::load_alphabet();
+    let before = current_alphabet.clone();
+    let added = add_glyphs(&mut current_alphabet, &letters);
+    if added > 0 {
+        save_alphabet_change(
+            format!("Import {added} letters from {}", preset.name),
+            &before,
+            &current_alphabet,
+        )?;
     }
+    Ok(added)
+}
 
-    if char_count > 1 {
-        panic!("Expected a single character. Received multiple characters.")
+/// Splits a scrambled word into the letters of the alphabet it consists of.
+///
+/// Letters that consist of multiple grapheme clusters, like "th", are kept together.
+/// If multiple letters match, the longest one wins. So, with both "t" and "th" in the alphabet,
+/// "th" is always read as a single letter.
+/// Parts of the word that don't match any letter are split into grapheme clusters.
+pub fn split_into_glyphs<'a>(word: &'a str, alphabet: &[Glyph]) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    let mut rest = word;
+    while !rest.is_empty() {
+        let length = alphabet
+            .iter()
+            .map(|glyph| glyph.symbol.as_str())
+            .filter(|symbol| !symbol.is_empty() && rest.starts_with(symbol))
+            .map(str::len)
+            .max()
+            .or_else(|| rest.graphemes(true).next().map(str::len))
+            .unwrap_or(rest.len());
+
+        let (glyph, remainder) = rest.split_at(length);
+        result.push(glyph);
+        rest = remainder;
     }
+    result
+}
 
-    if character.trim().is_empty() {
-        panic!("Expected a non-whitespace character. Received whitespace.")
+/// Blocks a word, so the scrambler never generates it again.
+///
+/// Blocking an already blocked word does not add it a second time. Only the reason is updated, if one is given.
+pub fn add_to_block_list(word: &str, reason: Option<&str>) -> Result<(), Box<dyn Error>> {
+    if word.trim().is_empty() {
+        return Err(ScramblerError("A blocked word must not be empty.".to_owned()).into());
     }
 
-    let mut current_alphabet = storage::load_alphabet();
-    let glyph = Glyph::new(character.to_owned());
+    let mut blocked_translations = storage::load_blocked_translations();
+    let before = blocked_translations.clone();
+    if block_list::insert_blocked(&mut blocked_translations, word, reason.map(str::to_owned)) {
+        save_block_list_change(format!("Block \"{word}\""), &before, &blocked_translations)?;
+    }
+    Ok(())
+}
+
+/// Searches the known translations and returns a single page of the results.
+pub fn search_dictionary(query: &dictionary::DictionaryQuery) -> dictionary::DictionaryPage {
+    dictionary::query(&storage::load_translated_words(), query)
+}
+
+/// Returns all blocked words, sorted.
+pub fn blocked_translations() -> BlockList {
+    storage::load_blocked_translations()
+}
+
+/// Returns all blocked words that contain `query`, ignoring case. Sorted.
+pub fn search_block_list(query: &str) -> BlockList {
+    filter_block_list(blocked_translations(), query)
+}
+
+/// Unblocks a word, so the scrambler can generate it again.
+pub fn remove_from_block_list(word: &str) -> Result<(), Box<dyn Error>> {
+    let mut blocked_translations = storage::load_blocked_translations();
+    let before = blocked_translations.clone();
+    if blocked_translations.remove(word).is_none() {
+        return Err(ScramblerError("The word \"".to_owned() + word + "\" is not blocked.").into());
+    }
+
+    save_block_list_change(
+        format!("Unblock \"{word}\""),
+        &before,
+        &blocked_translations,
+    )
+}
+
+/// Undoes the most recent change to the translations, the alphabet or the block list.
+///
+/// Returns a description of the undone change, or `None` if there is nothing to undo.
+pub fn undo() -> Result<Option<String>, Box<dyn Error>> {
+    journal::undo()
+}
+
+/// Redoes the most recently undone change.
+///
+/// Returns a description of the redone change, or `None` if there is nothing to redo.
+/// Making a new change forgets everything that could be redone.
+pub fn redo() -> Result<Option<String>, Box<dyn Error>> {
+    journal::redo()
+}
+
+/// Returns the names of all parties that had words revealed to them, sorted.
+pub fn parties() -> Vec<String> {
+    storage::load_knowledge().into_keys().collect()
+}
+
+/// Returns the words revealed to the party. Empty for a party that doesn't know any words yet.
+pub fn party_knowledge(party: &str) -> PartyKnowledge {
+    storage::load_knowledge()
+        .remove(party.trim())
+        .unwrap_or_default()
+}
+
+/// Marks words as revealed to the party during the session on the given date.
+///
+/// Every word must have a translation. Returns the number of words that were newly revealed.
+pub fn reveal_words(
+    party: &str,
+    words: &[String],
+    session: NaiveDate,
+) -> Result<usize, Box<dyn Error>> {
+    let party = check_party_name(party)?;
+    let words = words
+        .iter()
+        .map(|word| strip_punctuation(word))
+        .collect_vec();
+    let mut knowledge = storage::load_knowledge();
+    let party_knowledge = knowledge.entry(party.to_owned()).or_default();
+    let changed = knowledge::reveal(
+        party_knowledge,
+        &storage::load_translated_words(),
+        &words,
+        session,
+    )?;
+    if changed > 0 {
+        storage::save_knowledge(&knowledge)?;
+    }
+    Ok(changed)
+}
+
+/// Forgets that words were revealed to the party, e.g. when they were revealed by mistake.
+///
+/// Returns the number of words that were forgotten. A party without revealed words is removed.
+pub fn hide_words(party: &str, words: &[String]) -> Result<usize, Box<dyn Error>> {
+    let party = check_party_name(party)?;
+    let words = words
+        .iter()
+        .map(|word| strip_punctuation(word))
+        .collect_vec();
+    let mut knowledge = storage::load_knowledge();
+    let Some(party_knowledge) = knowledge.get_mut(party) else {
+        return Ok(0);
+    };
+    let changed = knowledge::hide(party_knowledge, &words);
+    if party_knowledge.is_empty() {
+        knowledge.remove(party);
+    }
+    if changed > 0 {
+        storage::save_knowledge(&knowledge)?;
+    }
+    Ok(changed)
+}
+
+/// Lists all known translations, and which of them the party knows.
+pub fn party_vocabulary(party: &str) -> Vec<VocabularyEntry> {
+    knowledge::vocabulary(&storage::load_translated_words(), &party_knowledge(party))
+}
+
+/// Translates an English text, and tells for each word whether the party knows what it means.
+///
+/// Use [`knowledge::render`] to write it down with the revealed words in English.
+/// Every word must have a translation already.
+pub fn decipher_for_party(text: &str, party: &str) -> Result<Vec<DecipheredWord>, Box<dyn Error>> {
+    knowledge::decipher(
+        text,
+        &storage::load_translated_words(),
+        &party_knowledge(party),
+    )
+}
+
+fn check_party_name(party: &str) -> Result<&str, ScramblerError> {
+    let party = party.trim();
+    match party.is_empty() {
+        true => Err(ScramblerError("A party needs a name.".to_owned())),
+        false => Ok(party),
+    }
+}
+
+pub fn block_patterns() -> Vec<BlockPattern> {
+    storage::load_block_patterns()
+}
+
+/// Blocks every word matching the pattern. Already existing translations are not affected.
+pub fn add_block_pattern(pattern: BlockPattern) -> Result<(), Box<dyn Error>> {
+    pattern.validate()?;
+
+    let mut patterns = storage::load_block_patterns();
+    if patterns.contains(&pattern) {
+        return Ok(());
+    }
+
+    let before = patterns.clone();
+    let description = format!("Block words that {pattern}");
+    patterns.push(pattern);
+    save_block_patterns_change(description, &before, &patterns)
+}
+
+pub fn remove_block_pattern(pattern: &BlockPattern) -> Result<(), Box<dyn Error>> {
+    let mut patterns = storage::load_block_patterns();
+    let before = patterns.clone();
+    patterns.retain(|existing| existing != pattern);
+    if patterns.len() == before.len() {
+        return Err(ScramblerError(format!("There is no block pattern that {pattern}.")).into());
+    }
+
+    save_block_patterns_change(
+        format!("Stop blocking words that {pattern}"),
+        &before,
+        &patterns,
+    )
+}
+
+/// Saves the translations and remembers the change in the journal, so it can be undone.
+fn save_translations_change(
+    description: String,
+    before: &HashMap<String, Translation>,
+    after: &HashMap<String, Translation>,
+) -> Result<(), Box<dyn Error>> {
+    storage::save_translated_words(after)?;
+    journal::record(description, journal::translation_changes(before, after));
+    Ok(())
+}
+
+fn save_alphabet_change(
+    description: String,
+    before: &[Glyph],
+    after: &[Glyph],
+) -> Result<(), Box<dyn Error>> {
+    storage::save_alphabet(after)?;
+    journal::record(description, journal::alphabet_changes(before, after));
+    Ok(())
+}
+
+fn save_block_patterns_change(
+    description: String,
+    before: &[BlockPattern],
+    after: &[BlockPattern],
+) -> Result<(), Box<dyn Error>> {
+    storage::save_block_patterns(after)?;
+    journal::record(description, journal::block_pattern_changes(before, after));
+    Ok(())
+}
+
+fn save_block_list_change(
+    description: String,
+    before: &BlockList,
+    after: &BlockList,
+) -> Result<(), Box<dyn Error>> {
+    storage::save_blocked_translations(after)?;
+    journal::record(description, journal::block_list_changes(before, after));
+    Ok(())
+}
+
+/// Saves an edited or deleted translation, optionally blocking the old translation with a reason.
+/// Both are a single change in the journal, so they are undone together.
+fn save_edit(
+    description: String,
+    before: &HashMap<String, Translation>,
+    after: &HashMap<String, Translation>,
+    block: Option<(&String, String)>,
+) -> Result<(), Box<dyn Error>> {
+    storage::save_translated_words(after)?;
+    let mut changes = journal::translation_changes(before, after);
+
+    if let Some((old_translation, reason)) = block {
+        let mut blocked_translations = storage::load_blocked_translations();
+        let blocked_before = blocked_translations.clone();
+        if block_list::insert_blocked(&mut blocked_translations, old_translation, Some(reason)) {
+            storage::save_blocked_translations(&blocked_translations)?;
+            changes.extend(journal::block_list_changes(
+                &blocked_before,
+                &blocked_translations,
+            ));
+        }
+    }
+
+    journal::record(description, changes);
+    Ok(())
+}
+
+fn find_translation<'a>(
+    known_translations: &'a HashMap<String, Translation>,
+    word: &str,
+) -> Result<&'a Translation, ScramblerError> {
+    known_translations.get(word).ok_or_else(|| {
+        ScramblerError("The word \"".to_owned() + word + "\" has no translation yet.")
+    })
+}
+
+/// Returns the old translation if it was replaced.
+fn replace_entry(
+    known_translations: &mut HashMap<String, Translation>,
+    word: &str,
+    new_translation: &str,
+    blocked_translations: &BlockList,
+    block_patterns: &BlockPatternMatcher,
+) -> Result<Option<Translation>, Box<dyn Error>> {
+    let new_translation = new_translation.trim();
+    if new_translation.is_empty() || new_translation.chars().any(char::is_whitespace) {
+        return Err(ScramblerError(
+            "A translation must be a single word without whitespace.".to_owned(),
+        )
+        .into());
+    }
+
+    let old_translation = find_translation(known_translations, word)?.clone();
+    if old_translation.translation == new_translation {
+        return Ok(None);
+    }
+
+    check_translation_available(
+        new_translation,
+        blocked_translations,
+        block_patterns,
+        known_translations,
+    )?;
+    known_translations.insert(
+        word.to_owned(),
+        Translation::new(new_translation.to_owned()),
+    );
+    Ok(Some(old_translation))
+}
+
+/// Returns the new word as it was stored.
+fn rename_entry(
+    known_translations: &mut HashMap<String, Translation>,
+    old_word: &str,
+    new_word: &str,
+) -> Result<String, ScramblerError> {
+    let new_word = strip_punctuation(new_word.trim());
+    check_single_word(&new_word)?;
+
+    let translation = find_translation(known_translations, old_word)?.clone();
+    if old_word == new_word {
+        return Ok(new_word);
+    }
+    if known_translations.contains_key(&new_word) {
+        return Err(ScramblerError(
+            "The word \"".to_owned() + &new_word + "\" already has a translation.",
+        ));
+    }
+
+    known_translations.remove(old_word);
+    known_translations.insert(new_word.clone(), translation);
+    Ok(new_word)
+}
+
+fn check_single_word(word: &str) -> Result<(), ScramblerError> {
+    match word.split_whitespace().count() {
+        1 => Ok(()),
+        _ => Err(ScramblerError(
+            "Error! I can only translate single words. The input \"".to_owned()
+                + word
+                + "\" is not a single word.",
+        )),
+    }
+}
 
-    if !current_alphabet
+/// Checks that a translation may be used for a word: it's not blocked and not used by another word yet.
+fn check_translation_available(
+    translation: &str,
+    blocked_translations: &BlockList,
+    block_patterns: &BlockPatternMatcher,
+    known_translations: &HashMap<String, Translation>,
+) -> Result<(), ScramblerError> {
+    if blocked_translations.contains_key(translation) || block_patterns.matches(translation) {
+        return Err(ScramblerError(
+            "The translation \"".to_owned() + translation + "\" is blocked.",
+        ));
+    }
+
+    if let Some((word, _)) = known_translations
         .iter()
-        .any(|char| char.symbol == glyph.symbol)
+        .find(|(_, existing)| existing.translation == translation)
     {
-        current_alphabet.push(glyph)
+        return Err(ScramblerError(format!(
+            "The translation \"{translation}\" is already used for \"{word}\"."
+        )));
     }
+    Ok(())
+}
 
-    storage::save_alphabet(&current_alphabet)
+/// A letter can consist of multiple characters, like "th". But it can't contain whitespace.
+fn check_letter(symbol: &str) -> Result<(), ScramblerError> {
+    match symbol.is_empty() || symbol.chars().any(char::is_whitespace) {
+        true => Err(ScramblerError(
+            "A letter must not be empty or contain whitespace.".to_owned(),
+        )),
+        false => Ok(()),
+    }
 }
 
-pub fn add_to_block_list(word: &str) -> Result<(), Box<dyn Error>> {
-    let mut blocked_translations = storage::load_blocked_translations();
-    blocked_translations.push(word.to_owned());
+fn find_glyph(alphabet: &[Glyph], symbol: &str) -> Result<usize, Box<dyn Error>> {
+    alphabet
+        .iter()
+        .position(|glyph| glyph.symbol == symbol)
+        .ok_or_else(|| {
+            ScramblerError("The letter \"".to_owned() + symbol + "\" is not in the alphabet.")
+                .into()
+        })
+}
 
-    storage::save_blocked_translations(blocked_translations)
+fn words_using_glyph(
+    symbol: &str,
+    alphabet: &[Glyph],
+    known_translations: &HashMap<String, Translation>,
+) -> Vec<String> {
+    known_translations
+        .iter()
+        .filter(|(_, translation)| {
+            split_into_glyphs(&translation.translation, alphabet).contains(&symbol)
+        })
+        .map(|(word, _)| word.clone())
+        .sorted()
+        .collect()
+}
+
+fn add_glyphs(alphabet: &mut Vec<Glyph>, symbols: &[&str]) -> usize {
+    let mut added = 0;
+    for symbol in symbols {
+        if !alphabet.iter().any(|glyph| glyph.symbol == *symbol) {
+            alphabet.push(Glyph::new(symbol.to_string()));
+            added += 1;
+        }
+    }
+    added
 }
 
 fn translate_word_impl(word: &str) -> Result<Translation, Box<dyn Error>> {
@@ -132,21 +879,157 @@ fn translate_word_impl(word: &str) -> Result<Translation, Box<dyn Error>> {
     }
 
     let blocked_translations = storage::load_blocked_translations();
+    let block_patterns = BlockPatternMatcher::new(&storage::load_block_patterns());
+
+    generator::new_translation(&word, &|candidate| {
+        translation_is_rejected(candidate, &blocked_translations, &known_translations)
+            || block_patterns.matches(candidate)
+    })
+}
+
+fn filter_block_list(blocked_translations: BlockList, query: &str) -> BlockList {
+    let query = query.to_lowercase();
+    blocked_translations
+        .into_iter()
+        .filter(|(blocked, _)| blocked.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Returns `true` if `known_translations` changed.
+fn insert_translation(
+    known_translations: &mut HashMap<String, Translation>,
+    word: &str,
+    translation: Translation,
+    overwrite: bool,
+) -> Result<bool, Box<dyn Error>> {
+    match known_translations.get(word) {
+        Some(existing) if existing.translation == translation.translation => Ok(false),
+        Some(existing) if !overwrite => Err(TranslationConflictError {
+            word: word.to_owned(),
+            existing: existing.clone(),
+            new: translation,
+        }
+        .into()),
+        _ => {
+            if let Some(other) = find_original_word(&translation.translation, known_translations) {
+                return Err(ScramblerError(format!(
+                    "The translation \"{}\" is already used for \"{other}\".",
+                    translation.translation
+                ))
+                .into());
+            }
+            known_translations.insert(word.to_owned(), translation);
+            Ok(true)
+        }
+    }
+}
+
+fn merge_translations(
+    known_translations: &mut HashMap<String, Translation>,
+    translations: HashMap<String, Translation>,
+    alphabet: &[Glyph],
+    blocked_translations: &BlockList,
+    block_patterns: &BlockPatternMatcher,
+    overwrite: bool,
+) -> Result<usize, Box<dyn Error>> {
+    let mut merged = known_translations.clone();
+    let mut changed = 0;
+    for (word, translation) in translations {
+        let word = strip_punctuation(word.trim());
+        check_single_word(&word)?;
+        let scrambled_word = translation.translation.clone();
+        if !insert_translation(&mut merged, &word, translation, overwrite)? {
+            continue;
+        }
+
+        // Check against all other words, including the ones imported before this one.
+        let inserted = merged
+            .remove(&word)
+            .expect("The translation was just inserted");
+        for letter in split_into_glyphs(&scrambled_word, alphabet) {
+            find_glyph(alphabet, letter)?;
+        }
+        check_translation_available(
+            &scrambled_word,
+            blocked_translations,
+            block_patterns,
+            &merged,
+        )?;
+        merged.insert(word, inserted);
+        changed += 1;
+    }
+
+    *known_translations = merged;
+    Ok(changed)
+}
+
+/// Checks a translation the user made up against everything a generated translation is checked against.
+fn validate_translation(
+    word: &str,
+    translation: &str,
+    alphabet: &[Glyph],
+    word_rules: &WordRules,
+    blocked_translations: &BlockList,
+    block_patterns: &BlockPatternMatcher,
+    known_translations: &HashMap<String, Translation>,
+) -> Result<(), InvalidTranslationError> {
+    if translation.is_empty() || translation.chars().any(char::is_whitespace) {
+        return Err(InvalidTranslationError::NotASingleWord);
+    }
+
+    let letters = split_into_glyphs(translation, alphabet);
+    if let Some(letter) = letters
+        .iter()
+        .find(|letter| find_glyph(alphabet, letter).is_err())
+    {
+        return Err(InvalidTranslationError::UnknownLetter(letter.to_string()));
+    }
+
+    let (min, max) =
+        generator::calculate_new_length(word.graphemes(true).count(), &word_rules.length);
+    if !(min..=max).contains(&letters.len()) {
+        return Err(InvalidTranslationError::WrongLength {
+            length: letters.len(),
+            min,
+            max,
+        });
+    }
 
-    let mut new_translation = generator::new_translation(&word)?;
-    while translation_is_rejected(&new_translation, &blocked_translations, &known_translations) {
-        new_translation = generator::new_translation(&word)?;
+    word_rules
+        .check(&letters)
+        .map_err(InvalidTranslationError::BrokenRule)?;
+
+    if let Some(blocked) = blocked_translations.get(translation) {
+        return Err(InvalidTranslationError::Blocked(blocked.reason.clone()));
+    }
+    if let Some(pattern) = block_patterns.find_match(translation) {
+        return Err(InvalidTranslationError::BlockedByPattern(pattern));
     }
 
-    Ok(new_translation)
+    if let Some(other) = find_original_word(translation, known_translations) {
+        if other != word {
+            return Err(InvalidTranslationError::AlreadyUsed(other.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn find_original_word<'a>(
+    scrambled_word: &str,
+    known_translations: &'a HashMap<String, Translation>,
+) -> Option<&'a String> {
+    known_translations
+        .iter()
+        .find(|(_, translation)| translation.translation == scrambled_word)
+        .map(|(word, _)| word)
 }
 
 fn translation_is_rejected(
-    new_translation: &Translation,
-    blocked_translations: &Vec<String>,
+    new_translation: &str,
+    blocked_translations: &BlockList,
     known_translations: &HashMap<String, Translation>,
 ) -> bool {
-    blocked_translations.contains(&new_translation.translation)
+    blocked_translations.contains_key(new_translation)
         || translation_already_exists(new_translation, known_translations)
 }
 
@@ -155,16 +1038,16 @@ fn word_has_translation(word: &str, known_translations: &HashMap<String, Transla
 }
 
 fn translation_already_exists(
-    new_translation: &Translation,
+    new_translation: &str,
     known_translations: &HashMap<String, Translation>,
 ) -> bool {
     known_translations
-        .iter()
-        .map(|(_key, value)| &value.translation)
-        .contains(&new_translation.translation)
+        .values()
+        .any(|value| value.translation == new_translation)
 }
 
-fn strip_punctuation(word: &str) -> String {
+/// Removes all punctuation from a word, as is done before translating it.
+pub fn strip_punctuation(word: &str) -> String {
     let regex = Regex::new(r"([[:punct:]])").expect("Hardcoded regex must be valid");
     let result = regex.replace_all(word, "");
     result.into_owned()
@@ -181,8 +1064,81 @@ impl fmt::Display for ScramblerError {
 
 impl Error for ScramblerError {}
 
+/// Returned when a translation chosen by the user breaks one of the rules for translations.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidTranslationError {
+    NotASingleWord,
+    UnknownLetter(String),
+    WrongLength {
+        length: usize,
+        min: usize,
+        max: usize,
+    },
+    BrokenRule(RuleViolation),
+    /// Contains the reason the translation was blocked, if any.
+    Blocked(Option<String>),
+    BlockedByPattern(BlockPattern),
+    /// Contains the word that already uses the translation.
+    AlreadyUsed(String),
+}
+
+impl fmt::Display for InvalidTranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidTranslationError::NotASingleWord => {
+                write!(f, "The translation must be a single word.")
+            }
+            InvalidTranslationError::UnknownLetter(letter) => {
+                write!(f, "The letter '{letter}' is not in the alphabet.")
+            }
+            InvalidTranslationError::WrongLength { length, min, max } => write!(
+                f,
+                "The translation has {length} letters, but must have between {min} and {max} letters."
+            ),
+            InvalidTranslationError::BrokenRule(violation) => {
+                write!(f, "The translation breaks a word rule: {violation}.")
+            }
+            InvalidTranslationError::Blocked(Some(reason)) => {
+                write!(f, "The translation is on the block list: {reason}.")
+            }
+            InvalidTranslationError::Blocked(None) => {
+                write!(f, "The translation is on the block list.")
+            }
+            InvalidTranslationError::BlockedByPattern(pattern) => {
+                write!(f, "The translation is blocked, because it {pattern}.")
+            }
+            InvalidTranslationError::AlreadyUsed(word) => {
+                write!(f, "The translation is already used for \"{word}\".")
+            }
+        }
+    }
+}
+
+impl Error for InvalidTranslationError {}
+
+/// Returned when saving a translation for a word that already has a different translation.
+#[derive(Debug)]
+pub struct TranslationConflictError {
+    pub word: String,
+    pub existing: Translation,
+    pub new: Translation,
+}
+
+impl fmt::Display for TranslationConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The word \"{}\" is already translated as \"{}\". Overwrite it with \"{}\"?",
+            self.word, self.existing.translation, self.new.translation
+        )
+    }
+}
+
+impl Error for TranslationConflictError {}
+
 #[cfg(test)]
 mod tests {
+    use super::block_list::BlockedTranslation;
     use super::*;
 
     #[test]
@@ -200,26 +1156,383 @@ mod tests {
 
     #[test]
     fn reject_blocked_translation() {
-        let new = Translation::new("foo".to_owned());
-        let blocked = vec!["foo".to_owned()];
+        let new = "foo";
+        let blocked = BlockList::from([("foo".to_owned(), BlockedTranslation::new(None))]);
         let known = HashMap::from([("bar".to_owned(), Translation::new("drink".to_owned()))]);
-        assert!(translation_is_rejected(&new, &blocked, &known));
+        assert!(translation_is_rejected(new, &blocked, &known));
     }
 
     #[test]
     fn reject_known_translation() {
-        let new = Translation::new("foo".to_owned());
-        let blocked = vec!["bar".to_owned()];
+        let new = "foo";
+        let blocked = BlockList::from([("bar".to_owned(), BlockedTranslation::new(None))]);
         let known = HashMap::from([("hello".to_owned(), Translation::new("foo".to_owned()))]);
-        assert!(translation_is_rejected(&new, &blocked, &known));
+        assert!(translation_is_rejected(new, &blocked, &known));
     }
 
     #[test]
     fn accept_new_translation() {
-        let new = Translation::new("foo".to_owned());
-        let blocked = vec!["bar".to_owned()];
+        let new = "foo";
+        let blocked = BlockList::from([("bar".to_owned(), BlockedTranslation::new(None))]);
         let known = HashMap::from([("hello".to_owned(), Translation::new("world".to_owned()))]);
-        assert!(!translation_is_rejected(&new, &blocked, &known));
+        assert!(!translation_is_rejected(new, &blocked, &known));
+    }
+
+    fn alphabet(symbols: &[&str]) -> Vec<Glyph> {
+        symbols
+            .iter()
+            .map(|symbol| Glyph::new(symbol.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn add_glyphs_skips_existing() {
+        let mut current = alphabet(&["a", "b"]);
+        assert_eq!(add_glyphs(&mut current, &["b", "c", "c"]), 1);
+        assert_eq!(
+            current
+                .iter()
+                .map(|glyph| glyph.symbol.as_str())
+                .collect_vec(),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn search_block_list_ignores_case() {
+        let blocked = BlockList::from([
+            ("Αβγ".to_owned(), BlockedTranslation::new(None)),
+            ("δεζ".to_owned(), BlockedTranslation::new(None)),
+        ]);
+        assert_eq!(
+            filter_block_list(blocked.clone(), "αΒ")
+                .keys()
+                .collect_vec(),
+            vec!["Αβγ"]
+        );
+        assert_eq!(filter_block_list(blocked, "").len(), 2);
+    }
+
+    #[test]
+    fn insert_new_translation() {
+        let mut known = HashMap::new();
+        let result =
+            insert_translation(&mut known, "foo", Translation::new("bar".to_owned()), false);
+        assert!(result.unwrap());
+        assert_eq!(known["foo"].translation, "bar");
+    }
+
+    #[test]
+    fn refuse_to_overwrite_translation() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        let result =
+            insert_translation(&mut known, "foo", Translation::new("baz".to_owned()), false);
+        let error = result
+            .unwrap_err()
+            .downcast::<TranslationConflictError>()
+            .unwrap();
+        assert_eq!(error.existing.translation, "bar");
+        assert_eq!(known["foo"].translation, "bar");
+    }
+
+    #[test]
+    fn overwrite_translation() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        let result =
+            insert_translation(&mut known, "foo", Translation::new("baz".to_owned()), true);
+        assert!(result.unwrap());
+        assert_eq!(known["foo"].translation, "baz");
+    }
+
+    #[test]
+    fn saving_same_translation_is_no_conflict() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        let result =
+            insert_translation(&mut known, "foo", Translation::new("bar".to_owned()), false);
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn refuse_translation_of_another_word() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        for overwrite in [false, true] {
+            let result = insert_translation(
+                &mut known,
+                "baz",
+                Translation::new("bar".to_owned()),
+                overwrite,
+            );
+            assert!(result.is_err());
+            assert!(!known.contains_key("baz"));
+        }
+    }
+
+    #[test]
+    fn letters_without_whitespace() {
+        assert!(check_letter("a").is_ok());
+        assert!(check_letter("th").is_ok());
+        assert!(check_letter("").is_err());
+        assert!(check_letter("t h").is_err());
+    }
+
+    #[test]
+    fn rename_keeps_translation() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        assert!(rename_entry(&mut known, "foo", "baz").is_ok());
+        assert_eq!(known["baz"].translation, "bar");
+        assert!(!known.contains_key("foo"));
+    }
+
+    #[test]
+    fn replace_keeps_the_same_translation() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        let blocked = BlockList::new();
+        let patterns = BlockPatternMatcher::new(&[]);
+        let replaced = replace_entry(&mut known, "foo", " bar ", &blocked, &patterns);
+        assert_eq!(replaced.unwrap(), None);
+        let replaced = replace_entry(&mut known, "foo", "baz", &blocked, &patterns);
+        assert_eq!(replaced.unwrap().unwrap().translation, "bar");
+        assert_eq!(known["foo"].translation, "baz");
+        assert!(replace_entry(&mut known, "foo", "two words", &blocked, &patterns).is_err());
+    }
+
+    #[test]
+    fn rename_ignores_punctuation() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        assert_eq!(rename_entry(&mut known, "foo", " baz! ").unwrap(), "baz");
+        assert_eq!(known["baz"].translation, "bar");
+        assert!(rename_entry(&mut known, "baz", "?!").is_err());
+        assert_eq!(known.len(), 1);
+    }
+
+    #[test]
+    fn rename_refuses_existing_word() {
+        let mut known = HashMap::from([
+            ("foo".to_owned(), Translation::new("bar".to_owned())),
+            ("baz".to_owned(), Translation::new("qux".to_owned())),
+        ]);
+        assert!(rename_entry(&mut known, "foo", "baz").is_err());
+        assert!(rename_entry(&mut known, "missing", "other").is_err());
+        assert!(rename_entry(&mut known, "foo", "two words").is_err());
+        assert_eq!(known.len(), 2);
+    }
+
+    #[test]
+    fn translation_available() {
+        let blocked = BlockList::from([("blocked".to_owned(), BlockedTranslation::new(None))]);
+        let patterns = BlockPatternMatcher::new(&[BlockPattern::Substring("xx".to_owned())]);
+        let known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        assert!(check_translation_available("new", &blocked, &patterns, &known).is_ok());
+        assert!(check_translation_available("blocked", &blocked, &patterns, &known).is_err());
+        assert!(check_translation_available("axxa", &blocked, &patterns, &known).is_err());
+        assert!(check_translation_available("bar", &blocked, &patterns, &known).is_err());
+    }
+
+    #[test]
+    fn merge_is_all_or_nothing() {
+        let mut known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        let imported = HashMap::from([
+            ("foo".to_owned(), Translation::new("baz".to_owned())),
+            ("new".to_owned(), Translation::new("wen".to_owned())),
+        ]);
+        let alphabet = "abefnorwz"
+            .chars()
+            .map(|letter| Glyph::new(letter.to_string()))
+            .collect_vec();
+        let merge = |known: &mut HashMap<String, Translation>,
+                     imported: HashMap<String, Translation>,
+                     overwrite: bool| {
+            merge_translations(
+                known,
+                imported,
+                &alphabet,
+                &BlockList::new(),
+                &BlockPatternMatcher::new(&[]),
+                overwrite,
+            )
+        };
+        assert!(merge(&mut known, imported.clone(), false).is_err());
+        assert_eq!(known.len(), 1);
+        assert_eq!(known["foo"].translation, "bar");
+
+        assert_eq!(merge(&mut known, imported, true).unwrap(), 2);
+        assert_eq!(known["foo"].translation, "baz");
+        assert_eq!(known["new"].translation, "wen");
+    }
+
+    #[test]
+    fn merge_validates_translations() {
+        let alphabet = "abnorw"
+            .chars()
+            .map(|letter| Glyph::new(letter.to_string()))
+            .collect_vec();
+        let blocked = BlockList::from([("bob".to_owned(), BlockedTranslation::new(None))]);
+        let patterns = BlockPatternMatcher::new(&[BlockPattern::Substring("aa".to_owned())]);
+        let known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        let merge = |imported: &[(&str, &str)]| {
+            let imported = imported
+                .iter()
+                .map(|(word, translation)| {
+                    (word.to_string(), Translation::new(translation.to_string()))
+                })
+                .collect();
+            let mut merged = known.clone();
+            let result =
+                merge_translations(&mut merged, imported, &alphabet, &blocked, &patterns, false);
+            if result.is_err() {
+                assert_eq!(merged, known);
+            }
+            result
+        };
+
+        assert_eq!(merge(&[("foo", "bar"), ("new", "won")]).unwrap(), 1);
+        assert!(merge(&[("new", "bar")]).is_err());
+        assert!(merge(&[("new", "won"), ("now", "won")]).is_err());
+        assert!(merge(&[("new", "bob")]).is_err());
+        assert!(merge(&[("new", "baab")]).is_err());
+        assert!(merge(&[("new", "wen")]).is_err());
+        assert!(merge(&[("", "won")]).is_err());
+        assert!(merge(&[("two words", "won")]).is_err());
+
+        let imported = HashMap::from([(" new! ".to_owned(), Translation::new("won".to_owned()))]);
+        let mut merged = known.clone();
+        merge_translations(&mut merged, imported, &alphabet, &blocked, &patterns, false).unwrap();
+        assert_eq!(merged["new"].translation, "won");
+    }
+
+    fn validate(translation: &str) -> Result<(), InvalidTranslationError> {
+        let alphabet = ["a", "b", "th"]
+            .iter()
+            .map(|symbol| Glyph::new(symbol.to_string()))
+            .collect_vec();
+        let rules = WordRules {
+            forbidden_substrings: vec!["thb".to_owned()],
+            ..Default::default()
+        };
+        let blocked = BlockList::from([(
+            "abba".to_owned(),
+            BlockedTranslation::new(Some("silly".to_owned())),
+        )]);
+        let patterns = BlockPatternMatcher::new(&[BlockPattern::Substring("thth".to_owned())]);
+        let known = HashMap::from([
+            ("word".to_owned(), Translation::new("abab".to_owned())),
+            ("other".to_owned(), Translation::new("baba".to_owned())),
+        ]);
+        validate_translation(
+            "word",
+            translation,
+            &alphabet,
+            &rules,
+            &blocked,
+            &patterns,
+            &known,
+        )
+    }
+
+    #[test]
+    fn validate_reports_broken_rule() {
+        assert_eq!(validate("athab"), Ok(()));
+        assert_eq!(validate("abab"), Ok(()));
+        assert_eq!(
+            validate("a b"),
+            Err(InvalidTranslationError::NotASingleWord)
+        );
+        assert_eq!(
+            validate("abc"),
+            Err(InvalidTranslationError::UnknownLetter("c".to_owned()))
+        );
+        assert_eq!(
+            validate("thababababa"),
+            Err(InvalidTranslationError::WrongLength {
+                length: 10,
+                min: 2,
+                max: 8
+            })
+        );
+        assert_eq!(
+            validate("aaa"),
+            Err(InvalidTranslationError::BrokenRule(
+                RuleViolation::TooManyRepeats {
+                    letter: "a".to_owned(),
+                    max: 2
+                }
+            ))
+        );
+        assert_eq!(
+            validate("athba"),
+            Err(InvalidTranslationError::BrokenRule(
+                RuleViolation::ForbiddenSubstring("thb".to_owned())
+            ))
+        );
+        assert_eq!(
+            validate("abba"),
+            Err(InvalidTranslationError::Blocked(Some("silly".to_owned())))
+        );
+        assert_eq!(
+            validate("athth"),
+            Err(InvalidTranslationError::BlockedByPattern(
+                BlockPattern::Substring("thth".to_owned())
+            ))
+        );
+        assert_eq!(
+            validate("baba"),
+            Err(InvalidTranslationError::AlreadyUsed("other".to_owned()))
+        );
+    }
+
+    #[test]
+    fn find_original() {
+        let known = HashMap::from([("foo".to_owned(), Translation::new("bar".to_owned()))]);
+        assert_eq!(find_original_word("bar", &known), Some(&"foo".to_owned()));
+        assert_eq!(find_original_word("foo", &known), None);
+    }
+
+    #[test]
+    fn find_missing_glyph() {
+        let current = alphabet(&["a", "b"]);
+        assert_eq!(find_glyph(&current, "b").unwrap(), 1);
+        assert!(find_glyph(&current, "c").is_err());
+    }
+
+    #[test]
+    fn words_using_glyph_respects_digraphs() {
+        let current = alphabet(&["t", "h", "th", "e"]);
+        let known = HashMap::from([
+            ("the".to_owned(), Translation::new("the".to_owned())),
+            ("bet".to_owned(), Translation::new("het".to_owned())),
+            ("eh".to_owned(), Translation::new("eh".to_owned())),
+        ]);
+        assert_eq!(words_using_glyph("t", &current, &known), vec!["bet"]);
+        assert_eq!(words_using_glyph("h", &current, &known), vec!["bet", "eh"]);
+        assert_eq!(words_using_glyph("th", &current, &known), vec!["the"]);
+    }
+
+    #[test]
+    fn split_single_graphemes() {
+        let alphabet = alphabet(&["a", "b"]);
+        assert_eq!(split_into_glyphs("aba", &alphabet), vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn split_keeps_digraphs_together() {
+        let alphabet = alphabet(&["t", "h", "th", "e"]);
+        assert_eq!(split_into_glyphs("the", &alphabet), vec!["th", "e"]);
+        assert_eq!(split_into_glyphs("hte", &alphabet), vec!["h", "t", "e"]);
+    }
+
+    #[test]
+    fn split_keeps_combining_marks_together() {
+        let alphabet = alphabet(&["ᚠ\u{0301}ᚢ"]);
+        assert_eq!(
+            split_into_glyphs("ᚠ\u{0301}ᚢᚠ\u{0301}", &alphabet),
+            vec!["ᚠ\u{0301}ᚢ", "ᚠ\u{0301}"]
+        );
+    }
+
+    #[test]
+    fn split_unknown_letters_into_graphemes() {
+        let alphabet = alphabet(&["th"]);
+        assert_eq!(split_into_glyphs("xthy", &alphabet), vec!["x", "th", "y"]);
     }
 
     #[test]
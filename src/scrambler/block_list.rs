@@ -0,0 +1,202 @@
+use log::error;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::time::SystemTime;
+
+/// The block list: every blocked word, with some details about why and when it was blocked.
+pub type BlockList = BTreeMap<String, BlockedTranslation>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BlockedTranslation {
+    pub reason: Option<String>,
+    /// Words that were blocked before reasons and timestamps were stored, get the Unix epoch.
+    pub time_added: SystemTime,
+}
+
+impl BlockedTranslation {
+    pub fn new(reason: Option<String>) -> BlockedTranslation {
+        BlockedTranslation {
+            reason,
+            time_added: SystemTime::now(),
+        }
+    }
+}
+
+/// Adds a word to the block list. Blocking a word twice keeps the original entry,
+/// only the reason is updated if a new one is given.
+/// Returns `true` if the block list changed.
+pub fn insert_blocked(block_list: &mut BlockList, word: &str, reason: Option<String>) -> bool {
+    match block_list.get_mut(word) {
+        Some(existing) => match reason {
+            Some(reason) if existing.reason.as_ref() != Some(&reason) => {
+                existing.reason = Some(reason);
+                true
+            }
+            _ => false,
+        },
+        None => {
+            block_list.insert(word.to_owned(), BlockedTranslation::new(reason));
+            true
+        }
+    }
+}
+
+/// Blocks a whole family of words at once, instead of a single word.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", content = "pattern", rename_all = "snake_case")]
+pub enum BlockPattern {
+    /// Blocks every word that contains the text.
+    Substring(String),
+    /// Blocks every word that matches the regex.
+    Regex(String),
+}
+
+impl BlockPattern {
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        match self {
+            BlockPattern::Substring(substring) if substring.is_empty() => Err(
+                InvalidBlockPatternError("An empty substring would block every word.".to_owned())
+                    .into(),
+            ),
+            BlockPattern::Substring(_) => Ok(()),
+            BlockPattern::Regex(pattern) => match Regex::new(pattern) {
+                Ok(_) => Ok(()),
+                Err(error) => Err(InvalidBlockPatternError(format!(
+                    "The pattern '{pattern}' is not a valid regex: {error}"
+                ))
+                .into()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for BlockPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockPattern::Substring(substring) => write!(f, "contains \"{substring}\""),
+            BlockPattern::Regex(pattern) => write!(f, "matches /{pattern}/"),
+        }
+    }
+}
+
+/// Checks words against many block patterns, compiling every regex only once.
+pub struct BlockPatternMatcher {
+    substrings: Vec<String>,
+    regexes: Vec<Regex>,
+}
+
+impl BlockPatternMatcher {
+    /// Invalid patterns are logged and skipped.
+    pub fn new(patterns: &[BlockPattern]) -> BlockPatternMatcher {
+        let mut substrings = Vec::new();
+        let mut regexes = Vec::new();
+        for pattern in patterns {
+            match pattern {
+                BlockPattern::Substring(substring) if !substring.is_empty() => {
+                    substrings.push(substring.clone())
+                }
+                BlockPattern::Substring(_) => {}
+                BlockPattern::Regex(regex) => match Regex::new(regex) {
+                    Ok(regex) => regexes.push(regex),
+                    Err(error) => error!("Ignoring the invalid block pattern '{regex}': {error}"),
+                },
+            }
+        }
+
+        BlockPatternMatcher {
+            substrings,
+            regexes,
+        }
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        self.find_match(word).is_some()
+    }
+
+    /// Returns the first pattern that blocks `word`.
+    pub fn find_match(&self, word: &str) -> Option<BlockPattern> {
+        if let Some(substring) = self
+            .substrings
+            .iter()
+            .find(|substring| word.contains(substring.as_str()))
+        {
+            return Some(BlockPattern::Substring(substring.clone()));
+        }
+        self.regexes
+            .iter()
+            .find(|regex| regex.is_match(word))
+            .map(|regex| BlockPattern::Regex(regex.as_str().to_owned()))
+    }
+}
+
+#[derive(Debug)]
+struct InvalidBlockPatternError(String);
+
+impl fmt::Display for InvalidBlockPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for InvalidBlockPatternError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocking_twice_keeps_one_entry() {
+        let mut block_list = BlockList::new();
+        assert!(insert_blocked(&mut block_list, "foo", None));
+        let time_added = block_list["foo"].time_added;
+        assert!(!insert_blocked(&mut block_list, "foo", None));
+        assert!(insert_blocked(
+            &mut block_list,
+            "foo",
+            Some("silly".to_owned())
+        ));
+        assert_eq!(block_list.len(), 1);
+        assert_eq!(block_list["foo"].reason, Some("silly".to_owned()));
+        assert_eq!(block_list["foo"].time_added, time_added);
+    }
+
+    #[test]
+    fn substring_pattern() {
+        let matcher = BlockPatternMatcher::new(&[BlockPattern::Substring("ab".to_owned())]);
+        assert!(matcher.matches("cabc"));
+        assert!(!matcher.matches("cbac"));
+    }
+
+    #[test]
+    fn regex_pattern() {
+        let matcher = BlockPatternMatcher::new(&[BlockPattern::Regex("^x.*y$".to_owned())]);
+        assert!(matcher.matches("xaay"));
+        assert!(!matcher.matches("axaay"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_ignored() {
+        let matcher = BlockPatternMatcher::new(&[
+            BlockPattern::Regex("(".to_owned()),
+            BlockPattern::Substring("".to_owned()),
+        ]);
+        assert!(!matcher.matches("anything"));
+    }
+
+    #[test]
+    fn validate_patterns() {
+        assert!(BlockPattern::Substring("".to_owned()).validate().is_err());
+        assert!(BlockPattern::Regex("(".to_owned()).validate().is_err());
+        assert!(BlockPattern::Regex("a+".to_owned()).validate().is_ok());
+    }
+
+    #[test]
+    fn serialize_pattern() {
+        let json = serde_json::to_string(&BlockPattern::Regex("a+".to_owned())).unwrap();
+        assert_eq!(json, r#"{"type":"regex","pattern":"a+"}"#);
+    }
+}
@@ -0,0 +1,260 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::Translation;
+
+/// Which side of the dictionary a search looks at.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchDirection {
+    #[default]
+    Both,
+    English,
+    Scrambled,
+}
+
+impl SearchDirection {
+    pub const ALL: [SearchDirection; 3] = [
+        SearchDirection::Both,
+        SearchDirection::English,
+        SearchDirection::Scrambled,
+    ];
+}
+
+impl fmt::Display for SearchDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchDirection::Both => write!(f, "English and scrambled"),
+            SearchDirection::English => write!(f, "English only"),
+            SearchDirection::Scrambled => write!(f, "Scrambled only"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DictionarySort {
+    #[default]
+    English,
+    Scrambled,
+    TimeAdded,
+}
+
+impl DictionarySort {
+    pub const ALL: [DictionarySort; 3] = [
+        DictionarySort::English,
+        DictionarySort::Scrambled,
+        DictionarySort::TimeAdded,
+    ];
+}
+
+impl fmt::Display for DictionarySort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DictionarySort::English => write!(f, "Sort by English"),
+            DictionarySort::Scrambled => write!(f, "Sort by scrambled"),
+            DictionarySort::TimeAdded => write!(f, "Sort by date added"),
+        }
+    }
+}
+
+/// A search through the known translations. Only a single page of results is returned.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DictionaryQuery {
+    /// Matches words containing this text, ignoring case. Empty matches everything.
+    pub search: String,
+    pub direction: SearchDirection,
+    pub sort: DictionarySort,
+    pub descending: bool,
+    /// Zero based.
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl Default for DictionaryQuery {
+    fn default() -> Self {
+        DictionaryQuery {
+            search: String::new(),
+            direction: SearchDirection::default(),
+            sort: DictionarySort::default(),
+            descending: false,
+            page: 0,
+            page_size: 50,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DictionaryPage {
+    pub entries: Vec<(String, Translation)>,
+    /// The number of words matching the search, over all pages.
+    pub total_matches: usize,
+    /// The page that was returned. Can be lower than the requested page if there are fewer results.
+    pub page: usize,
+    pub page_count: usize,
+}
+
+pub fn query(
+    known_translations: &HashMap<String, Translation>,
+    query: &DictionaryQuery,
+) -> DictionaryPage {
+    let search = query.search.trim().to_lowercase();
+    let mut matches: Vec<(&String, &Translation)> = known_translations
+        .iter()
+        .filter(|(word, translation)| {
+            search.is_empty()
+                || match query.direction {
+                    SearchDirection::Both => {
+                        word.to_lowercase().contains(&search)
+                            || translation.translation.to_lowercase().contains(&search)
+                    }
+                    SearchDirection::English => word.to_lowercase().contains(&search),
+                    SearchDirection::Scrambled => {
+                        translation.translation.to_lowercase().contains(&search)
+                    }
+                }
+        })
+        .collect();
+
+    // The word itself is always the last key, so the order is stable.
+    match query.sort {
+        DictionarySort::English => {
+            matches.sort_by_cached_key(|(word, _)| (word.to_lowercase(), word.to_string()))
+        }
+        DictionarySort::Scrambled => {
+            matches.sort_by(|a, b| (&a.1.translation, a.0).cmp(&(&b.1.translation, b.0)))
+        }
+        DictionarySort::TimeAdded => {
+            matches.sort_by(|a, b| (a.1.time_added, a.0).cmp(&(b.1.time_added, b.0)))
+        }
+    }
+    if query.descending {
+        matches.reverse();
+    }
+
+    let page_size = query.page_size.max(1);
+    let total_matches = matches.len();
+    let page_count = total_matches.div_ceil(page_size).max(1);
+    let page = query.page.min(page_count - 1);
+    let entries = matches
+        .into_iter()
+        .skip(page * page_size)
+        .take(page_size)
+        .map(|(word, translation)| (word.clone(), translation.clone()))
+        .collect();
+
+    DictionaryPage {
+        entries,
+        total_matches,
+        page,
+        page_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    fn dictionary() -> HashMap<String, Translation> {
+        [("cat", "ᚲᚨᛏ", 3), ("Dog", "δογ", 1), ("bird", "ᛒα", 2)]
+            .into_iter()
+            .map(|(word, translation, seconds)| {
+                (
+                    word.to_owned(),
+                    Translation {
+                        translation: translation.to_owned(),
+                        time_added: SystemTime::UNIX_EPOCH + Duration::from_secs(seconds),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn words(page: &DictionaryPage) -> Vec<&str> {
+        page.entries.iter().map(|(word, _)| word.as_str()).collect()
+    }
+
+    #[test]
+    fn sort_orders() {
+        let mut query = DictionaryQuery::default();
+        assert_eq!(
+            words(&super::query(&dictionary(), &query)),
+            ["bird", "cat", "Dog"]
+        );
+
+        query.sort = DictionarySort::Scrambled;
+        assert_eq!(
+            words(&super::query(&dictionary(), &query)),
+            ["Dog", "cat", "bird"]
+        );
+
+        query.sort = DictionarySort::TimeAdded;
+        query.descending = true;
+        assert_eq!(
+            words(&super::query(&dictionary(), &query)),
+            ["cat", "bird", "Dog"]
+        );
+    }
+
+    #[test]
+    fn search_both_directions() {
+        let mut query = DictionaryQuery {
+            search: "D".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(words(&super::query(&dictionary(), &query)), ["bird", "Dog"]);
+
+        query.search = "ΔΟ".to_owned();
+        assert_eq!(words(&super::query(&dictionary(), &query)), ["Dog"]);
+
+        query.direction = SearchDirection::English;
+        assert!(super::query(&dictionary(), &query).entries.is_empty());
+
+        query.search = "ᛒ".to_owned();
+        query.direction = SearchDirection::Scrambled;
+        assert_eq!(words(&super::query(&dictionary(), &query)), ["bird"]);
+    }
+
+    #[test]
+    fn paging() {
+        let mut query = DictionaryQuery {
+            page_size: 2,
+            ..Default::default()
+        };
+        let page = super::query(&dictionary(), &query);
+        assert_eq!(words(&page), ["bird", "cat"]);
+        assert_eq!((page.page, page.page_count, page.total_matches), (0, 2, 3));
+
+        query.page = 1;
+        assert_eq!(words(&super::query(&dictionary(), &query)), ["Dog"]);
+
+        query.page = 10;
+        let page = super::query(&dictionary(), &query);
+        assert_eq!(page.page, 1);
+        assert_eq!(words(&page), ["Dog"]);
+    }
+
+    #[test]
+    fn empty_dictionary_has_one_page() {
+        let page = super::query(&HashMap::new(), &DictionaryQuery::default());
+        assert_eq!((page.page, page.page_count, page.total_matches), (0, 1, 0));
+    }
+
+    #[test]
+    fn query_from_json_uses_defaults() {
+        let query: DictionaryQuery =
+            serde_json::from_str(r#"{"search": "cat", "sort": "time_added"}"#).unwrap();
+        assert_eq!(
+            query,
+            DictionaryQuery {
+                search: "cat".to_owned(),
+                sort: DictionarySort::TimeAdded,
+                ..Default::default()
+            }
+        );
+    }
+}
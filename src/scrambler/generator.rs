@@ -1,77 +1,707 @@
+use std::collections::HashMap;
 use std::error::Error;
 
 use itertools::Itertools;
+use log::debug;
+use log::warn;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rand::seq::SliceRandom;
 use rand::Rng;
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::scrambler::storage;
 
+use super::rules::CompiledWordRules;
+use super::rules::LengthDistribution;
+use super::rules::LengthRules;
+use super::rules::RuleViolation;
+use super::rules::WordRules;
+use super::split_into_glyphs;
 use super::Glyph;
+use super::ScramblerError;
 use super::Translation;
 
-pub fn new_translation(word: &str) -> Result<Translation, Box<dyn Error>> {
+/// How many times the generator starts over for a single length, each time with new random letters.
+const MAX_ATTEMPTS: usize = 50;
+
+/// The maximum amount of letters the generator tries to place in a single attempt.
+/// A short budget with many restarts keeps an unlucky first letter from using up all the time,
+/// e.g. when a regex rule rejects every word starting with that letter.
+const MAX_STEPS_PER_ATTEMPT: usize = 2_000;
+
+/// Generates a new word for `word`.
+///
+/// The word is constructed letter by letter, only picking letters that don't break the word rules.
+/// When the generator runs into a dead end, it backtracks. So, invalid words are never generated,
+/// no matter how small the alphabet or how strict the rules.
+///
+/// # Arguments
+///
+/// * `word` - The English word to generate a translation for.
+/// * `is_taken` - Returns `true` for words that can't be used, e.g. blocked or already existing words.
+pub fn new_translation(
+    word: &str,
+    is_taken: &dyn Fn(&str) -> bool,
+) -> Result<Translation, Box<dyn Error>> {
+    let mut translations = new_translations(word, 1, is_taken)?;
+    Ok(translations.remove(0))
+}
+
+/// Generates up to `count` different new words for `word`, like [`new_translation`].
+///
+/// Fewer words are returned if the word rules, block list and existing words don't leave enough options.
+/// If not a single word can be generated, an error is returned.
+pub fn new_translations(
+    word: &str,
+    count: usize,
+    is_taken: &dyn Fn(&str) -> bool,
+) -> Result<Vec<Translation>, Box<dyn Error>> {
     let alphabet = storage::load_alphabet();
+    if alphabet.is_empty() {
+        return Err(ScramblerError(
+            "The alphabet is empty. Please add some letters before translating.".to_owned(),
+        )
+        .into());
+    }
+    let rules = storage::load_word_rules();
+
+    generate(
+        word,
+        count,
+        &alphabet,
+        &rules,
+        is_taken,
+        &mut rand::thread_rng(),
+    )
+}
 
+fn generate(
+    word: &str,
+    count: usize,
+    alphabet: &[Glyph],
+    rules: &WordRules,
+    is_taken: &dyn Fn(&str) -> bool,
+    random_generator: &mut impl Rng,
+) -> Result<Vec<Translation>, Box<dyn Error>> {
+    let compiled_rules = rules.compile()?;
     let original_length = word.graphemes(true).count();
-    let mut result = create_random_word(&alphabet, original_length);
-    while !is_valid_word(&result) {
-        result = create_random_word(&alphabet, original_length)
+
+    let mut generated: Vec<String> = Vec::with_capacity(count);
+    let mut rejections = HashMap::new();
+    let mut budget_exhausted = false;
+    while generated.len() < count {
+        let is_taken_or_generated =
+            |candidate: &str| is_taken(candidate) || generated.iter().any(|word| word == candidate);
+
+        // Every word gets its own lengths, so the words differ in length too.
+        let lengths = shuffle_lengths(original_length, &rules.length, random_generator);
+        budget_exhausted = false;
+        rejections = HashMap::new();
+        let mut new_word = None;
+        for length in lengths {
+            let search = search_word(
+                length,
+                alphabet,
+                &compiled_rules,
+                &is_taken_or_generated,
+                &mut rejections,
+                random_generator,
+            );
+            match search {
+                Search::Found(word) => {
+                    new_word = Some(word);
+                    break;
+                }
+                Search::Impossible => {}
+                Search::BudgetExhausted => budget_exhausted = true,
+            }
+        }
+
+        match new_word {
+            Some(new_word) => generated.push(new_word),
+            None => break,
+        }
     }
 
-    Ok(Translation::new(result))
+    if generated.is_empty() {
+        let reason = match budget_exhausted {
+            true => "The search budget was exhausted before a valid word was found. The word rules may reject most words only once they are complete, e.g. with a regex",
+            false => "The word rules, block list and existing words leave no options",
+        };
+        return Err(ScramblerError(format!(
+            "Could not generate a valid translation for \"{word}\". {reason}. Most common reasons for rejecting a letter: {}.",
+            summarize_rejections(&rejections)
+        ))
+        .into());
+    }
+    Ok(generated.into_iter().map(Translation::new).collect())
 }
 
-fn is_valid_word(word: &str) -> bool {
-    let graphemes = word.graphemes(true);
-    for (a, b, c) in graphemes.tuple_windows() {
-        if a == b && b == c {
-            return false;
+/// The outcome of looking for a word of a single length.
+#[derive(Debug, PartialEq)]
+enum Search {
+    Found(String),
+    /// Every option was tried, no word of this length follows the rules.
+    Impossible,
+    /// No word was found, but not every option was tried.
+    BudgetExhausted,
+}
+
+/// Looks for a valid word of `length` letters. Every attempt starts over with new random letters
+/// and gets its own step budget.
+fn search_word(
+    length: usize,
+    alphabet: &[Glyph],
+    rules: &CompiledWordRules,
+    is_taken: &dyn Fn(&str) -> bool,
+    rejections: &mut HashMap<String, usize>,
+    random_generator: &mut impl Rng,
+) -> Search {
+    for _ in 0..MAX_ATTEMPTS {
+        let mut construction = Construction {
+            alphabet,
+            rules,
+            is_taken,
+            remaining_steps: MAX_STEPS_PER_ATTEMPT,
+            rejections: std::mem::take(rejections),
+        };
+        let mut letters = Vec::with_capacity(length);
+        let found = construction.construct(&mut letters, length, random_generator);
+        *rejections = construction.rejections;
+        if found {
+            return Search::Found(letters.concat());
+        }
+        // Steps are left, so the search tried every option before giving up.
+        if construction.remaining_steps > 0 {
+            return Search::Impossible;
         }
     }
-    true
+    Search::BudgetExhausted
+}
+
+struct Construction<'a> {
+    alphabet: &'a [Glyph],
+    rules: &'a CompiledWordRules<'a>,
+    is_taken: &'a dyn Fn(&str) -> bool,
+    remaining_steps: usize,
+    rejections: HashMap<String, usize>,
+}
+
+impl<'a> Construction<'a> {
+    /// Extends `letters` to a valid word of `length` letters.
+    /// Returns `false` and leaves `letters` untouched if that is impossible.
+    fn construct(
+        &mut self,
+        letters: &mut Vec<&'a str>,
+        length: usize,
+        random_generator: &mut impl Rng,
+    ) -> bool {
+        let mut candidates = self
+            .alphabet
+            .iter()
+            .map(|glyph| glyph.symbol.as_str())
+            .collect_vec();
+        candidates.shuffle(random_generator);
+
+        for candidate in candidates {
+            if self.remaining_steps == 0 {
+                return false;
+            }
+            self.remaining_steps -= 1;
+
+            if let Err(violation) = self.rules.check_next(letters, candidate, length) {
+                self.reject(violation.to_string());
+                continue;
+            }
+
+            letters.push(candidate);
+            if letters.len() == length {
+                match self.check_word(letters) {
+                    Ok(()) if (self.is_taken)(&letters.concat()) => {
+                        self.reject("the word is blocked or already in use".to_owned())
+                    }
+                    Ok(()) => return true,
+                    Err(violation) => self.reject(violation.to_string()),
+                }
+            } else if self.construct(letters, length, random_generator) {
+                return true;
+            }
+            letters.pop();
+        }
+
+        false
+    }
+
+    /// Checks the finished word against all rules.
+    ///
+    /// The word is checked both as it was constructed and as it will be read back.
+    /// These differ when letters combine into a multi-grapheme letter, e.g. "t" + "h" is read as "th".
+    fn check_word(&self, letters: &[&str]) -> Result<(), RuleViolation> {
+        self.rules.check(letters)?;
+
+        let word = letters.concat();
+        let read_back = split_into_glyphs(&word, self.alphabet);
+        if read_back != letters {
+            self.rules.check(&read_back)?;
+        }
+        Ok(())
+    }
+
+    fn reject(&mut self, reason: String) {
+        debug!("Rejected a letter: {reason}.");
+        *self.rejections.entry(reason).or_default() += 1;
+    }
+}
+
+fn summarize_rejections(rejections: &HashMap<String, usize>) -> String {
+    rejections
+        .iter()
+        .sorted_by(|l, r| r.1.cmp(l.1))
+        .take(3)
+        .map(|(reason, count)| format!("{reason} ({count} times)"))
+        .join(", ")
+}
+
+/// Returns all allowed lengths for a new word in the order the generator should try them.
+///
+/// The order is random, following the distribution from the `length_rules`.
+/// Lengths that have no chance of being picked are left out.
+fn shuffle_lengths(
+    original_length: usize,
+    length_rules: &LengthRules,
+    random_generator: &mut impl Rng,
+) -> Vec<usize> {
+    let (min_length, max_length) = calculate_new_length(original_length, length_rules);
+    let mut weights = length_weights(original_length, min_length, max_length, length_rules);
+    if let Err(error) = WeightedIndex::new(&weights) {
+        warn!("The length distribution can't be used for a word of length {original_length}, falling back to a uniform distribution. Reason: {error}");
+        weights = vec![1; weights.len()];
+    }
+
+    let mut result = Vec::with_capacity(weights.len());
+    while let Ok(distribution) = WeightedIndex::new(&weights) {
+        let index = distribution.sample(random_generator);
+        weights[index] = 0;
+        result.push(min_length + index);
+    }
+    result
+}
+
+/// Calculates the range of allowed lengths for a new word.
+///
+/// The length is counted in letters of the alphabet, so a multi-grapheme letter like "th" counts as one.
+///
+/// The lower bound is rounded towards 0, the upper bound towards infinity.
+/// Both bounds are clamped to the limits in the `length_rules`.
+pub(super) fn calculate_new_length(
+    original_length: usize,
+    length_rules: &LengthRules,
+) -> (usize, usize) {
+    // Shave off floating point noise, so 0.3 * 10 doesn't get rounded up to 4.
+    const EPSILON: f64 = 1e-9;
+
+    let min_length = (original_length as f64 * length_rules.min_ratio + EPSILON).floor() as usize;
+    let min_length = min_length.max(length_rules.min_length).max(1);
+
+    let max_length = (original_length as f64 * length_rules.max_ratio - EPSILON).ceil() as usize;
+    let max_length = match length_rules.max_length {
+        Some(limit) => max_length.min(limit),
+        None => max_length,
+    };
+
+    (min_length, max_length.max(min_length))
+}
+
+/// Returns the weight for every length from `min_length` up to and including `max_length`.
+fn length_weights(
+    original_length: usize,
+    min_length: usize,
+    max_length: usize,
+    length_rules: &LengthRules,
+) -> Vec<u32> {
+    let lengths = min_length..=max_length;
+    match &length_rules.distribution {
+        LengthDistribution::Uniform => lengths.map(|_| 1).collect(),
+        LengthDistribution::Triangular => {
+            let center = original_length.clamp(min_length, max_length);
+            let peak = (center - min_length).max(max_length - center) + 1;
+            lengths
+                .map(|length| (peak - length.abs_diff(center)) as u32)
+                .collect()
+        }
+        LengthDistribution::Table { weights } => lengths
+            .map(|length| {
+                let difference = length as i64 - original_length as i64;
+                weights
+                    .iter()
+                    .filter(|entry| entry.difference == difference)
+                    .map(|entry| entry.weight)
+                    .sum()
+            })
+            .collect(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::is_valid_word;
+    use super::super::rules::LengthWeight;
+    use super::super::rules::RegexConstraint;
+    use super::*;
 
     #[test]
-    fn triple_char_is_not_valid_word() {
-        assert!(!is_valid_word("aaa"))
+    fn length_of_single_letter() {
+        assert_eq!(calculate_new_length(1, &LengthRules::default()), (1, 2));
     }
 
     #[test]
-    fn double_char_is_valid_word() {
-        assert!(is_valid_word("aa"))
+    fn length_rounds_lower_bound_down() {
+        assert_eq!(calculate_new_length(3, &LengthRules::default()), (1, 6));
+        assert_eq!(calculate_new_length(5, &LengthRules::default()), (2, 10));
     }
 
     #[test]
-    fn triple_char_with_other_char_in_between_is_valid_word() {
-        assert!(is_valid_word("aabaa"))
+    fn length_of_even_word() {
+        assert_eq!(calculate_new_length(4, &LengthRules::default()), (2, 8));
+    }
+
+    #[test]
+    fn length_rounds_upper_bound_up() {
+        let rules = LengthRules {
+            max_ratio: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(calculate_new_length(3, &rules), (1, 5));
     }
-}
 
-fn create_random_word(alphabet: &Vec<Glyph>, original_length: usize) -> String {
-    let mut random_generator = rand::thread_rng();
+    #[test]
+    fn length_ignores_floating_point_noise() {
+        let rules = LengthRules {
+            min_ratio: 0.3,
+            max_ratio: 0.3,
+            ..Default::default()
+        };
+        assert_eq!(calculate_new_length(10, &rules), (3, 3));
+    }
 
-    let (min_length, max_length) = calculate_new_length(original_length);
-    let length = random_generator.gen_range(min_length..=max_length);
+    #[test]
+    fn length_is_clamped() {
+        let rules = LengthRules {
+            min_length: 3,
+            max_length: Some(7),
+            ..Default::default()
+        };
+        assert_eq!(calculate_new_length(2, &rules), (3, 4));
+        assert_eq!(calculate_new_length(10, &rules), (5, 7));
+    }
 
-    let mut result = String::with_capacity(length);
-    for _ in 0..length {
-        let random_glyph = &alphabet[random_generator.gen_range(0..alphabet.len())];
-        result.push_str(&random_glyph.symbol);
+    #[test]
+    fn length_range_is_never_empty() {
+        let rules = LengthRules {
+            min_length: 5,
+            max_length: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(calculate_new_length(2, &rules), (5, 5));
     }
 
-    result
-}
+    #[test]
+    fn triangular_weights_peak_at_original_length() {
+        let rules = LengthRules {
+            distribution: LengthDistribution::Triangular,
+            ..Default::default()
+        };
+        assert_eq!(length_weights(3, 1, 6, &rules), vec![2, 3, 4, 3, 2, 1]);
+    }
 
-fn calculate_new_length(original_length: usize) -> (usize, usize) {
-    let mut min_length = original_length / 2;
-    if min_length == 0 {
-        min_length = 1;
+    #[test]
+    fn table_weights_follow_difference() {
+        let rules = LengthRules {
+            distribution: LengthDistribution::Table {
+                weights: vec![
+                    LengthWeight {
+                        difference: 0,
+                        weight: 5,
+                    },
+                    LengthWeight {
+                        difference: -1,
+                        weight: 2,
+                    },
+                ],
+            },
+            ..Default::default()
+        };
+        assert_eq!(length_weights(3, 1, 6, &rules), vec![0, 2, 5, 0, 0, 0]);
+    }
+
+    #[test]
+    fn shuffled_lengths_are_in_range() {
+        let mut random_generator = rand::thread_rng();
+        let rules = LengthRules {
+            distribution: LengthDistribution::Triangular,
+            ..Default::default()
+        };
+        let lengths = shuffle_lengths(3, &rules, &mut random_generator);
+        assert_eq!(
+            lengths.iter().sorted().collect_vec(),
+            vec![&1, &2, &3, &4, &5, &6]
+        );
+    }
+
+    #[test]
+    fn shuffled_lengths_skip_zero_weights() {
+        let mut random_generator = rand::thread_rng();
+        let rules = LengthRules {
+            distribution: LengthDistribution::Table {
+                weights: vec![LengthWeight {
+                    difference: 1,
+                    weight: 1,
+                }],
+            },
+            ..Default::default()
+        };
+        assert_eq!(shuffle_lengths(3, &rules, &mut random_generator), vec![4]);
+    }
+
+    #[test]
+    fn empty_table_falls_back_to_uniform() {
+        let mut random_generator = rand::thread_rng();
+        let rules = LengthRules {
+            distribution: LengthDistribution::Table {
+                weights: Vec::new(),
+            },
+            ..Default::default()
+        };
+        assert_eq!(shuffle_lengths(3, &rules, &mut random_generator).len(), 6);
+    }
+
+    fn glyphs(symbols: &[&str]) -> Vec<Glyph> {
+        symbols
+            .iter()
+            .map(|symbol| Glyph::new(symbol.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn construct_with_tiny_alphabet() {
+        let alphabet = glyphs(&["a", "b"]);
+        let rules = WordRules {
+            max_consecutive_repeats: Some(1),
+            must_start_with: vec!["b".to_owned()],
+            ..Default::default()
+        };
+        let compiled = rules.compile().unwrap();
+        let mut construction = Construction {
+            alphabet: &alphabet,
+            rules: &compiled,
+            is_taken: &|_| false,
+            remaining_steps: MAX_STEPS_PER_ATTEMPT,
+            rejections: HashMap::new(),
+        };
+        let mut letters = Vec::new();
+        assert!(construction.construct(&mut letters, 5, &mut rand::thread_rng()));
+        assert_eq!(letters.concat(), "babab");
+    }
+
+    #[test]
+    fn construct_counts_digraph_as_single_letter() {
+        let alphabet = glyphs(&["th"]);
+        let rules = WordRules::default();
+        let compiled = rules.compile().unwrap();
+        let mut construction = Construction {
+            alphabet: &alphabet,
+            rules: &compiled,
+            is_taken: &|_| false,
+            remaining_steps: MAX_STEPS_PER_ATTEMPT,
+            rejections: HashMap::new(),
+        };
+        let mut letters = Vec::new();
+        assert!(construction.construct(&mut letters, 2, &mut rand::thread_rng()));
+        assert_eq!(letters.concat(), "thth");
     }
-    let max_length = original_length * 2;
 
-    (min_length, max_length)
+    #[test]
+    fn construct_checks_word_as_read_back() {
+        let alphabet = glyphs(&["t", "h", "th"]);
+        let rules = WordRules {
+            max_consecutive_repeats: Some(1),
+            ..Default::default()
+        };
+        let compiled = rules.compile().unwrap();
+        let construction = Construction {
+            alphabet: &alphabet,
+            rules: &compiled,
+            is_taken: &|_| false,
+            remaining_steps: MAX_STEPS_PER_ATTEMPT,
+            rejections: HashMap::new(),
+        };
+        assert!(construction.check_word(&["t", "h", "th"]).is_err());
+    }
+
+    #[test]
+    fn construct_avoids_taken_words() {
+        let alphabet = glyphs(&["a", "b"]);
+        let rules = WordRules::default();
+        let compiled = rules.compile().unwrap();
+        let mut construction = Construction {
+            alphabet: &alphabet,
+            rules: &compiled,
+            is_taken: &|word| word != "ba",
+            remaining_steps: MAX_STEPS_PER_ATTEMPT,
+            rejections: HashMap::new(),
+        };
+        let mut letters = Vec::new();
+        assert!(construction.construct(&mut letters, 2, &mut rand::thread_rng()));
+        assert_eq!(letters.concat(), "ba");
+    }
+
+    #[test]
+    fn construct_gives_up_when_impossible() {
+        let alphabet = glyphs(&["a"]);
+        let rules = WordRules::default();
+        let compiled = rules.compile().unwrap();
+        let mut construction = Construction {
+            alphabet: &alphabet,
+            rules: &compiled,
+            is_taken: &|_| false,
+            remaining_steps: MAX_STEPS_PER_ATTEMPT,
+            rejections: HashMap::new(),
+        };
+        let mut letters = Vec::new();
+        assert!(!construction.construct(&mut letters, 3, &mut rand::thread_rng()));
+        assert!(letters.is_empty());
+        assert!(!construction.rejections.is_empty());
+    }
+
+    #[test]
+    fn generate_different_words() {
+        let alphabet = glyphs(&["a", "b", "c"]);
+        let translations = generate(
+            "abc",
+            5,
+            &alphabet,
+            &WordRules::default(),
+            &|word| word == "abc",
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        assert_eq!(translations.len(), 5);
+        assert!(translations.iter().map(|t| &t.translation).all_unique());
+        assert!(translations.iter().all(|t| t.translation != "abc"));
+    }
+
+    #[test]
+    fn generate_returns_fewer_words_when_options_run_out() {
+        let alphabet = glyphs(&["a", "b"]);
+        let rules = WordRules {
+            length: LengthRules {
+                min_ratio: 1.0,
+                max_ratio: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let translations = generate(
+            "x",
+            5,
+            &alphabet,
+            &rules,
+            &|_| false,
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        assert_eq!(translations.len(), 2);
+
+        let result = generate(
+            "x",
+            5,
+            &alphabet,
+            &rules,
+            &|_| true,
+            &mut rand::thread_rng(),
+        );
+        assert!(result.unwrap_err().to_string().contains("leave no options"));
+    }
+
+    #[test]
+    fn generate_recovers_from_a_bad_first_letter() {
+        // Only words starting with "a" are valid, but the rules can only tell once the word is complete.
+        // Starting with another letter leaves more options than a single attempt can try.
+        let alphabet = glyphs(&["a", "b", "c", "d"]);
+        let rules = WordRules {
+            regex_constraints: vec![RegexConstraint {
+                pattern: "^a".to_owned(),
+                must_match: true,
+            }],
+            length: LengthRules {
+                min_length: 10,
+                max_length: Some(10),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        for _ in 0..5 {
+            let translations = generate(
+                "x",
+                1,
+                &alphabet,
+                &rules,
+                &|_| false,
+                &mut rand::thread_rng(),
+            )
+            .unwrap();
+            assert!(translations[0].translation.starts_with('a'));
+        }
+    }
+
+    #[test]
+    fn search_tells_exhausted_budget_from_impossible() {
+        let alphabet = glyphs(&["a", "b", "c", "d"]);
+        let rules = WordRules {
+            regex_constraints: vec![RegexConstraint {
+                pattern: "^z".to_owned(),
+                must_match: true,
+            }],
+            ..Default::default()
+        };
+        let compiled = rules.compile().unwrap();
+        let mut rejections = HashMap::new();
+        let search = |length, rejections: &mut HashMap<String, usize>| {
+            search_word(
+                length,
+                &alphabet,
+                &compiled,
+                &|_| false,
+                rejections,
+                &mut rand::thread_rng(),
+            )
+        };
+        assert_eq!(search(2, &mut rejections), Search::Impossible);
+        assert_eq!(search(10, &mut rejections), Search::BudgetExhausted);
+
+        let error = generate(
+            "x",
+            1,
+            &alphabet,
+            &WordRules {
+                length: LengthRules {
+                    min_length: 10,
+                    max_length: Some(10),
+                    ..Default::default()
+                },
+                ..rules
+            },
+            &|_| false,
+            &mut rand::thread_rng(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("search budget was exhausted"));
+    }
+
+    #[test]
+    fn summary_lists_most_common_rejection_first() {
+        let rejections = HashMap::from([("foo".to_owned(), 1), ("bar".to_owned(), 5)]);
+        let summary = summarize_rejections(&rejections);
+        assert!(summary.starts_with("bar (5 times)"));
+    }
 }
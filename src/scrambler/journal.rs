@@ -0,0 +1,377 @@
+use log::error;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::SystemTime;
+
+use super::block_list::BlockList;
+use super::block_list::BlockPattern;
+use super::block_list::BlockedTranslation;
+use super::storage;
+use super::Glyph;
+use super::Translation;
+
+/// Older changes are forgotten, so the journal doesn't grow forever.
+const MAX_JOURNAL_LENGTH: usize = 100;
+
+/// The changes that were made to the stored data, so they can be undone and redone.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Journal {
+    /// The changes that can be undone, the most recent one last.
+    pub done: Vec<JournalEntry>,
+    /// The changes that were undone and can be redone, the most recently undone one last.
+    pub undone: Vec<JournalEntry>,
+}
+
+/// A single action of the user, like accepting a translation or blocking a word.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// Tells the user what undoing or redoing the entry does.
+    pub description: String,
+    pub time: SystemTime,
+    pub changes: Vec<Change>,
+}
+
+/// What part of the stored data looked like before and after an action.
+/// `None` means the word was not there.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Change {
+    Translation {
+        word: String,
+        before: Option<Translation>,
+        after: Option<Translation>,
+    },
+    Blocked {
+        word: String,
+        before: Option<BlockedTranslation>,
+        after: Option<BlockedTranslation>,
+    },
+    Alphabet {
+        before: Vec<Glyph>,
+        after: Vec<Glyph>,
+    },
+    BlockPattern {
+        before: Vec<BlockPattern>,
+        after: Vec<BlockPattern>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Version {
+    Before,
+    After,
+}
+
+impl Version {
+    fn pick<'a, T>(self, before: &'a T, after: &'a T) -> &'a T {
+        match self {
+            Version::Before => before,
+            Version::After => after,
+        }
+    }
+}
+
+impl Journal {
+    fn push(&mut self, entry: JournalEntry) {
+        self.done.push(entry);
+        if self.done.len() > MAX_JOURNAL_LENGTH {
+            self.done.remove(0);
+        }
+        self.undone.clear();
+    }
+}
+
+/// Remembers an action in the journal. Actions that didn't change anything are skipped.
+///
+/// The action itself is already saved at this point, so failing to save the journal is only logged.
+pub(super) fn record(description: String, changes: Vec<Change>) {
+    if changes.is_empty() {
+        return;
+    }
+
+    let mut journal = storage::load_journal();
+    journal.push(JournalEntry {
+        description,
+        time: SystemTime::now(),
+        changes,
+    });
+    if let Err(error) = storage::save_journal(&journal) {
+        error!("Failed to save the journal, this change can't be undone: {error}");
+    }
+}
+
+/// Undoes the most recent action. Returns its description, or `None` if there is nothing to undo.
+pub(super) fn undo() -> Result<Option<String>, Box<dyn Error>> {
+    let mut journal = storage::load_journal();
+    let Some(entry) = journal.done.pop() else {
+        return Ok(None);
+    };
+
+    restore(&entry.changes, Version::Before)?;
+    let description = entry.description.clone();
+    journal.undone.push(entry);
+    storage::save_journal(&journal)?;
+    Ok(Some(description))
+}
+
+/// Redoes the most recently undone action. Returns its description, or `None` if there is nothing to redo.
+pub(super) fn redo() -> Result<Option<String>, Box<dyn Error>> {
+    let mut journal = storage::load_journal();
+    let Some(entry) = journal.undone.pop() else {
+        return Ok(None);
+    };
+
+    restore(&entry.changes, Version::After)?;
+    let description = entry.description.clone();
+    journal.done.push(entry);
+    storage::save_journal(&journal)?;
+    Ok(Some(description))
+}
+
+fn restore(changes: &[Change], version: Version) -> Result<(), Box<dyn Error>> {
+    let mut known_translations = storage::load_translated_words();
+    let mut blocked_translations = storage::load_blocked_translations();
+    let mut alphabet = storage::load_alphabet();
+    let mut block_patterns = storage::load_block_patterns();
+    apply(
+        changes,
+        version,
+        &mut known_translations,
+        &mut blocked_translations,
+        &mut alphabet,
+        &mut block_patterns,
+    );
+
+    if changes
+        .iter()
+        .any(|change| matches!(change, Change::Translation { .. }))
+    {
+        storage::save_translated_words(&known_translations)?;
+    }
+    if changes
+        .iter()
+        .any(|change| matches!(change, Change::Blocked { .. }))
+    {
+        storage::save_blocked_translations(&blocked_translations)?;
+    }
+    if changes
+        .iter()
+        .any(|change| matches!(change, Change::Alphabet { .. }))
+    {
+        storage::save_alphabet(&alphabet)?;
+    }
+    if changes
+        .iter()
+        .any(|change| matches!(change, Change::BlockPattern { .. }))
+    {
+        storage::save_block_patterns(&block_patterns)?;
+    }
+    Ok(())
+}
+
+/// Puts the data back the way it was before or after the changes.
+fn apply(
+    changes: &[Change],
+    version: Version,
+    known_translations: &mut HashMap<String, Translation>,
+    blocked_translations: &mut BlockList,
+    alphabet: &mut Vec<Glyph>,
+    block_patterns: &mut Vec<BlockPattern>,
+) {
+    for change in changes {
+        match change {
+            Change::Translation {
+                word,
+                before,
+                after,
+            } => match version.pick(before, after) {
+                Some(translation) => {
+                    known_translations.insert(word.clone(), translation.clone());
+                }
+                None => {
+                    known_translations.remove(word);
+                }
+            },
+            Change::Blocked {
+                word,
+                before,
+                after,
+            } => match version.pick(before, after) {
+                Some(blocked) => {
+                    blocked_translations.insert(word.clone(), blocked.clone());
+                }
+                None => {
+                    blocked_translations.remove(word);
+                }
+            },
+            Change::Alphabet { before, after } => *alphabet = version.pick(before, after).clone(),
+            Change::BlockPattern { before, after } => {
+                *block_patterns = version.pick(before, after).clone()
+            }
+        }
+    }
+}
+
+/// Lists every word whose translation differs between `before` and `after`.
+pub(super) fn translation_changes(
+    before: &HashMap<String, Translation>,
+    after: &HashMap<String, Translation>,
+) -> Vec<Change> {
+    let words: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    words
+        .into_iter()
+        .filter(|word| before.get(*word) != after.get(*word))
+        .map(|word| Change::Translation {
+            word: word.clone(),
+            before: before.get(word).cloned(),
+            after: after.get(word).cloned(),
+        })
+        .collect()
+}
+
+/// Lists every word that was blocked, unblocked or got a different reason.
+pub(super) fn block_list_changes(before: &BlockList, after: &BlockList) -> Vec<Change> {
+    let words: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    words
+        .into_iter()
+        .filter(|word| before.get(*word) != after.get(*word))
+        .map(|word| Change::Blocked {
+            word: word.clone(),
+            before: before.get(word).cloned(),
+            after: after.get(word).cloned(),
+        })
+        .collect()
+}
+
+pub(super) fn alphabet_changes(before: &[Glyph], after: &[Glyph]) -> Vec<Change> {
+    match before == after {
+        true => vec![],
+        false => vec![Change::Alphabet {
+            before: before.to_vec(),
+            after: after.to_vec(),
+        }],
+    }
+}
+
+pub(super) fn block_pattern_changes(
+    before: &[BlockPattern],
+    after: &[BlockPattern],
+) -> Vec<Change> {
+    match before == after {
+        true => vec![],
+        false => vec![Change::BlockPattern {
+            before: before.to_vec(),
+            after: after.to_vec(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations(words: &[(&str, &str)]) -> HashMap<String, Translation> {
+        words
+            .iter()
+            .map(|(word, translation)| {
+                (
+                    word.to_string(),
+                    Translation {
+                        translation: translation.to_string(),
+                        time_added: SystemTime::UNIX_EPOCH,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn entry(description: &str) -> JournalEntry {
+        JournalEntry {
+            description: description.to_owned(),
+            time: SystemTime::UNIX_EPOCH,
+            changes: vec![],
+        }
+    }
+
+    #[test]
+    fn translation_changes_lists_added_changed_and_removed_words() {
+        let before = translations(&[("cat", "ᚲᚨᛏ"), ("dog", "δογ"), ("owl", "ᛟ")]);
+        let after = translations(&[("cat", "ᚲᚨᛏ"), ("dog", "ᛞᛟᚷ"), ("bat", "βατ")]);
+        let changes = translation_changes(&before, &after);
+        let words = changes
+            .iter()
+            .map(|change| match change {
+                Change::Translation { word, .. } => word.as_str(),
+                _ => panic!("Expected only translation changes"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(words, ["bat", "dog", "owl"]);
+    }
+
+    #[test]
+    fn apply_undoes_and_redoes() {
+        let before = translations(&[("cat", "ᚲᚨᛏ"), ("dog", "δογ")]);
+        let after = translations(&[("dog", "ᛞᛟᚷ"), ("bat", "βατ")]);
+        let mut blocked_before = BlockList::new();
+        blocked_before.insert("ᚲᚨᛏ".to_owned(), BlockedTranslation::new(None));
+        let blocked_after = BlockList::new();
+        let alphabet_before = vec![Glyph::new("ᚲ".to_owned())];
+        let alphabet_after = vec![];
+        let patterns_before = vec![];
+        let patterns_after = vec![BlockPattern::Substring("ᛟ".to_owned())];
+        let changes = [
+            translation_changes(&before, &after),
+            block_list_changes(&blocked_before, &blocked_after),
+            alphabet_changes(&alphabet_before, &alphabet_after),
+            block_pattern_changes(&patterns_before, &patterns_after),
+        ]
+        .concat();
+
+        let mut known_translations = after.clone();
+        let mut blocked_translations = blocked_after.clone();
+        let mut alphabet = alphabet_after.clone();
+        let mut block_patterns = patterns_after.clone();
+        apply(
+            &changes,
+            Version::Before,
+            &mut known_translations,
+            &mut blocked_translations,
+            &mut alphabet,
+            &mut block_patterns,
+        );
+        assert_eq!(known_translations, before);
+        assert_eq!(blocked_translations, blocked_before);
+        assert_eq!(alphabet, alphabet_before);
+        assert_eq!(block_patterns, patterns_before);
+
+        apply(
+            &changes,
+            Version::After,
+            &mut known_translations,
+            &mut blocked_translations,
+            &mut alphabet,
+            &mut block_patterns,
+        );
+        assert_eq!(known_translations, after);
+        assert_eq!(blocked_translations, blocked_after);
+        assert_eq!(alphabet, alphabet_after);
+        assert_eq!(block_patterns, patterns_after);
+    }
+
+    #[test]
+    fn new_entry_clears_redo_and_old_entries_are_forgotten() {
+        let mut journal = Journal {
+            done: vec![],
+            undone: vec![entry("undone")],
+        };
+        for index in 0..MAX_JOURNAL_LENGTH + 1 {
+            journal.push(entry(&index.to_string()));
+        }
+        assert!(journal.undone.is_empty());
+        assert_eq!(journal.done.len(), MAX_JOURNAL_LENGTH);
+        assert_eq!(journal.done[0].description, "1");
+    }
+}
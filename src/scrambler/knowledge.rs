@@ -0,0 +1,376 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use super::strip_punctuation;
+use super::Translation;
+
+/// What the players know of the language: per party, the words that were revealed to them.
+pub type Knowledge = BTreeMap<String, PartyKnowledge>;
+
+/// The words revealed to a single party, by English word.
+pub type PartyKnowledge = BTreeMap<String, RevealedWord>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RevealedWord {
+    /// The scrambled word the party learned. It is kept when the translation is changed later on.
+    pub translation: String,
+    /// The date of the session in which the word was revealed.
+    pub session: NaiveDate,
+}
+
+/// A known translation, and whether a party knows it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct VocabularyEntry {
+    pub word: String,
+    /// The current translation. For a revealed word that was deleted since, the revealed translation.
+    pub translation: String,
+    /// `None` if the word was not revealed to the party yet.
+    pub revealed: Option<RevealedWord>,
+}
+
+impl VocabularyEntry {
+    /// The party learned a translation that was changed afterwards.
+    pub fn is_outdated(&self) -> bool {
+        self.revealed
+            .as_ref()
+            .is_some_and(|revealed| revealed.translation != self.translation)
+    }
+}
+
+/// Reveals words to a party. A word that was already revealed keeps its session date,
+/// unless its translation changed since. Then the party learns the new translation.
+///
+/// Fails without revealing anything if one of the words has no translation.
+/// Returns the number of words that were newly revealed or updated.
+pub fn reveal(
+    party_knowledge: &mut PartyKnowledge,
+    known_translations: &HashMap<String, Translation>,
+    words: &[String],
+    session: NaiveDate,
+) -> Result<usize, Box<dyn Error>> {
+    if let Some(unknown) = words
+        .iter()
+        .find(|word| !known_translations.contains_key(*word))
+    {
+        return Err(UnknownWordError(unknown.clone()).into());
+    }
+
+    let mut changed = 0;
+    for word in words {
+        let translation = &known_translations[word].translation;
+        if party_knowledge
+            .get(word)
+            .is_some_and(|revealed| revealed.translation == *translation)
+        {
+            continue;
+        }
+        party_knowledge.insert(
+            word.clone(),
+            RevealedWord {
+                translation: translation.clone(),
+                session,
+            },
+        );
+        changed += 1;
+    }
+    Ok(changed)
+}
+
+/// Forgets that words were revealed to a party. Returns the number of words that were forgotten.
+pub fn hide(party_knowledge: &mut PartyKnowledge, words: &[String]) -> usize {
+    words
+        .iter()
+        .filter(|word| party_knowledge.remove(*word).is_some())
+        .count()
+}
+
+/// Lists every known translation and every revealed word, sorted by English word.
+pub fn vocabulary(
+    known_translations: &HashMap<String, Translation>,
+    party_knowledge: &PartyKnowledge,
+) -> Vec<VocabularyEntry> {
+    let mut entries: BTreeMap<&String, VocabularyEntry> = party_knowledge
+        .iter()
+        .map(|(word, revealed)| {
+            (
+                word,
+                VocabularyEntry {
+                    word: word.clone(),
+                    translation: revealed.translation.clone(),
+                    revealed: Some(revealed.clone()),
+                },
+            )
+        })
+        .collect();
+    for (word, translation) in known_translations {
+        entries
+            .entry(word)
+            .or_insert_with(|| VocabularyEntry {
+                word: word.clone(),
+                translation: translation.translation.clone(),
+                revealed: None,
+            })
+            .translation = translation.translation.clone();
+    }
+    entries.into_values().collect()
+}
+
+/// How revealed words are shown in a partially deciphered text.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RevealedStyle {
+    /// Revealed words are written in English.
+    #[default]
+    English,
+    /// Revealed words stay scrambled, followed by the English word, like `ᚲᚨᛏ (cat)`.
+    Gloss,
+}
+
+impl RevealedStyle {
+    pub const ALL: [RevealedStyle; 2] = [RevealedStyle::English, RevealedStyle::Gloss];
+}
+
+impl fmt::Display for RevealedStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevealedStyle::English => write!(f, "Revealed words in English"),
+            RevealedStyle::Gloss => write!(f, "Revealed words with an English gloss"),
+        }
+    }
+}
+
+/// A word of a text, as a party would read it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DecipheredWord {
+    /// The whitespace and punctuation in front of the word in the text, like an opening quote.
+    pub before: String,
+    /// Empty if there is only punctuation, like a dash between two words.
+    pub word: String,
+    pub translation: String,
+    /// The punctuation right after the word in the text, like a comma.
+    pub after: String,
+    /// The party knows what the word means.
+    pub revealed: bool,
+}
+
+impl DecipheredWord {
+    /// The word with the punctuation around it.
+    pub fn render(&self, style: RevealedStyle) -> String {
+        let word = match (self.revealed, style) {
+            (false, _) => self.translation.clone(),
+            (true, RevealedStyle::English) => self.word.clone(),
+            (true, RevealedStyle::Gloss) => format!("{} ({})", self.translation, self.word),
+        };
+        format!("{}{word}{}", self.before, self.after)
+    }
+}
+
+/// Translates an English text word by word, and tells for each word whether the party can read it.
+/// The whitespace and punctuation of the text are kept, so only the words themselves change.
+///
+/// A word only counts as revealed if the party learned its current translation.
+/// Fails if one of the words has no translation yet.
+pub fn decipher(
+    text: &str,
+    known_translations: &HashMap<String, Translation>,
+    party_knowledge: &PartyKnowledge,
+) -> Result<Vec<DecipheredWord>, Box<dyn Error>> {
+    let mut words = vec![];
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let start = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        let end = rest[start..]
+            .find(char::is_whitespace)
+            .map_or(rest.len(), |length| start + length);
+        let (before, word, after) = split_punctuation(&rest[start..end]);
+        let before = rest[..start].to_owned() + before;
+        rest = &rest[end..];
+
+        let word = strip_punctuation(word);
+        if word.is_empty() {
+            words.push(DecipheredWord {
+                before,
+                word,
+                translation: "".to_owned(),
+                after: after.to_owned(),
+                revealed: false,
+            });
+            continue;
+        }
+        let Some(translation) = known_translations.get(&word) else {
+            return Err(UnknownWordError(word).into());
+        };
+        let revealed = party_knowledge
+            .get(&word)
+            .is_some_and(|revealed| revealed.translation == translation.translation);
+        words.push(DecipheredWord {
+            before,
+            translation: translation.translation.clone(),
+            word,
+            after: after.to_owned(),
+            revealed,
+        });
+    }
+    Ok(words)
+}
+
+/// Splits a piece of text into the punctuation in front of a word, the word and the punctuation after it.
+/// Text that is only punctuation ends up in front of an empty word.
+fn split_punctuation(text: &str) -> (&str, &str, &str) {
+    let start = text
+        .find(|c: char| !c.is_ascii_punctuation())
+        .unwrap_or(text.len());
+    let end = text
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .len()
+        .max(start);
+    (&text[..start], &text[start..end], &text[end..])
+}
+
+/// Writes the deciphered text down, e.g. for a handout.
+pub fn render(words: &[DecipheredWord], style: RevealedStyle) -> String {
+    words.iter().map(|word| word.render(style)).collect()
+}
+
+#[derive(Debug)]
+struct UnknownWordError(String);
+
+impl fmt::Display for UnknownWordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The word \"{}\" has no translation yet. Translate it first.",
+            self.0
+        )
+    }
+}
+
+impl Error for UnknownWordError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations(words: &[(&str, &str)]) -> HashMap<String, Translation> {
+        words
+            .iter()
+            .map(|(word, translation)| {
+                (word.to_string(), Translation::new(translation.to_string()))
+            })
+            .collect()
+    }
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    #[test]
+    fn reveal_keeps_the_first_session() {
+        let known = translations(&[("cat", "ᚲᚨᛏ"), ("dog", "δογ")]);
+        let mut party = PartyKnowledge::new();
+        assert_eq!(
+            reveal(&mut party, &known, &words(&["cat"]), date(1)).unwrap(),
+            1
+        );
+        assert_eq!(
+            reveal(&mut party, &known, &words(&["cat", "dog"]), date(8)).unwrap(),
+            1
+        );
+        assert_eq!(party["cat"].session, date(1));
+        assert_eq!(party["dog"].session, date(8));
+    }
+
+    #[test]
+    fn reveal_unknown_word_changes_nothing() {
+        let known = translations(&[("cat", "ᚲᚨᛏ")]);
+        let mut party = PartyKnowledge::new();
+        assert!(reveal(&mut party, &known, &words(&["cat", "owl"]), date(1)).is_err());
+        assert!(party.is_empty());
+    }
+
+    #[test]
+    fn vocabulary_marks_known_unknown_and_outdated_words() {
+        let mut party = PartyKnowledge::new();
+        reveal(
+            &mut party,
+            &translations(&[("cat", "ᚲᚨᛏ"), ("owl", "ᛟ")]),
+            &words(&["cat", "owl"]),
+            date(1),
+        )
+        .unwrap();
+        let known = translations(&[("cat", "κατ"), ("dog", "δογ")]);
+
+        let entries = vocabulary(&known, &party);
+        let summary = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.word.as_str(),
+                    entry.translation.as_str(),
+                    entry.revealed.is_some(),
+                    entry.is_outdated(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                ("cat", "κατ", true, true),
+                ("dog", "δογ", false, false),
+                ("owl", "ᛟ", true, false),
+            ]
+        );
+
+        assert_eq!(hide(&mut party, &words(&["cat", "dog"])), 1);
+        assert!(!party.contains_key("cat"));
+    }
+
+    #[test]
+    fn render_only_revealed_words_in_english() {
+        let mut party = PartyKnowledge::new();
+        reveal(
+            &mut party,
+            &translations(&[("cat", "ᚲᚨᛏ"), ("owl", "ᛟ")]),
+            &words(&["cat", "owl"]),
+            date(1),
+        )
+        .unwrap();
+        let known = translations(&[("cat", "ᚲᚨᛏ"), ("dog", "δογ"), ("owl", "ᛟᚹ")]);
+
+        let deciphered = decipher("The cat, the dog and the owl!", &known, &party);
+        assert!(deciphered.is_err());
+
+        let deciphered = decipher("cat, dog owl!", &known, &party).unwrap();
+        assert_eq!(render(&deciphered, RevealedStyle::English), "cat, δογ ᛟᚹ!");
+        assert_eq!(
+            render(&deciphered, RevealedStyle::Gloss),
+            "ᚲᚨᛏ (cat), δογ ᛟᚹ!"
+        );
+    }
+
+    #[test]
+    fn render_keeps_the_punctuation_and_layout() {
+        let known = translations(&[("cat", "ᚲᚨᛏ"), ("dog", "δογ"), ("dont", "ᛞᛟ")]);
+        let party = PartyKnowledge::new();
+        let deciphered = decipher(" \"Cat?\" -- (dog)...\n  don't! ", &known, &party);
+        assert!(deciphered.is_err());
+
+        let deciphered = decipher(" \"cat?\" -- (dog)...\n  don't! ", &known, &party).unwrap();
+        assert_eq!(
+            render(&deciphered, RevealedStyle::English),
+            "\"ᚲᚨᛏ?\" -- (δογ)...\n  ᛞᛟ!"
+        );
+        assert_eq!(deciphered[1].word, "");
+    }
+}
@@ -0,0 +1,588 @@
+use std::error::Error;
+use std::fmt;
+
+use itertools::Itertools;
+
+#[derive(Debug)]
+pub struct AlphabetPreset {
+    /// Identifies the preset in the API, e.g. `"greek"`.
+    pub id: &'static str,
+    /// A human readable name.
+    pub name: &'static str,
+    pub letters: &'static [PresetLetter],
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PresetLetter {
+    pub symbol: &'static str,
+    /// The official Unicode name of the letter.
+    pub unicode_name: &'static str,
+    /// The usual Latin transliteration. Empty if there is none.
+    pub transliteration: &'static str,
+    /// Letters are grouped to make it easy to import a sensible subset, e.g. only the lowercase Greek letters.
+    pub group: &'static str,
+}
+
+/// Which letters of a preset to import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresetSelection {
+    All,
+    /// All letters of the given groups.
+    Groups(Vec<String>),
+    /// Only the given letters.
+    Letters(Vec<String>),
+}
+
+pub const PRESETS: &[AlphabetPreset] = &[
+    AlphabetPreset {
+        id: "phoenician",
+        name: "Phoenician",
+        letters: PHOENICIAN_LETTERS,
+    },
+    AlphabetPreset {
+        id: "greek",
+        name: "Greek",
+        letters: GREEK_LETTERS,
+    },
+    AlphabetPreset {
+        id: "runic",
+        name: "Elder Futhark (Runic)",
+        letters: RUNIC_LETTERS,
+    },
+];
+
+pub fn find_preset(id: &str) -> Option<&'static AlphabetPreset> {
+    PRESETS.iter().find(|preset| preset.id == id)
+}
+
+impl AlphabetPreset {
+    /// The groups of this preset, in the order they appear.
+    pub fn groups(&self) -> Vec<&'static str> {
+        self.letters
+            .iter()
+            .map(|letter| letter.group)
+            .unique()
+            .collect()
+    }
+
+    pub fn select(
+        &self,
+        selection: &PresetSelection,
+    ) -> Result<Vec<&'static PresetLetter>, Box<dyn Error>> {
+        match selection {
+            PresetSelection::All => Ok(self.letters.iter().collect()),
+            PresetSelection::Groups(groups) => {
+                let unknown = groups
+                    .iter()
+                    .filter(|group| !self.letters.iter().any(|letter| letter.group == *group))
+                    .collect_vec();
+                if !unknown.is_empty() {
+                    return Err(UnknownSelectionError {
+                        preset: self.name,
+                        kind: "groups",
+                        unknown: unknown.into_iter().join(", "),
+                    }
+                    .into());
+                }
+
+                Ok(self
+                    .letters
+                    .iter()
+                    .filter(|letter| groups.iter().any(|group| group == letter.group))
+                    .collect())
+            }
+            PresetSelection::Letters(symbols) => {
+                let unknown = symbols
+                    .iter()
+                    .filter(|symbol| !self.letters.iter().any(|letter| letter.symbol == *symbol))
+                    .collect_vec();
+                if !unknown.is_empty() {
+                    return Err(UnknownSelectionError {
+                        preset: self.name,
+                        kind: "letters",
+                        unknown: unknown.into_iter().join(", "),
+                    }
+                    .into());
+                }
+
+                Ok(self
+                    .letters
+                    .iter()
+                    .filter(|letter| symbols.iter().any(|symbol| symbol == letter.symbol))
+                    .collect())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct UnknownSelectionError {
+    preset: &'static str,
+    kind: &'static str,
+    unknown: String,
+}
+
+impl fmt::Display for UnknownSelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The {} alphabet does not contain the {}: {}",
+            self.preset, self.kind, self.unknown
+        )
+    }
+}
+
+impl Error for UnknownSelectionError {}
+
+const fn letter(
+    symbol: &'static str,
+    unicode_name: &'static str,
+    transliteration: &'static str,
+    group: &'static str,
+) -> PresetLetter {
+    PresetLetter {
+        symbol,
+        unicode_name,
+        transliteration,
+        group,
+    }
+}
+
+const PHOENICIAN_LETTERS: &[PresetLetter] = &[
+    letter("𐤀", "PHOENICIAN LETTER ALF", "ʾ", "Letters"),
+    letter("𐤁", "PHOENICIAN LETTER BET", "b", "Letters"),
+    letter("𐤂", "PHOENICIAN LETTER GAML", "g", "Letters"),
+    letter("𐤃", "PHOENICIAN LETTER DELT", "d", "Letters"),
+    letter("𐤄", "PHOENICIAN LETTER HE", "h", "Letters"),
+    letter("𐤅", "PHOENICIAN LETTER WAU", "w", "Letters"),
+    letter("𐤆", "PHOENICIAN LETTER ZAI", "z", "Letters"),
+    letter("𐤇", "PHOENICIAN LETTER HET", "ḥ", "Letters"),
+    letter("𐤈", "PHOENICIAN LETTER TET", "ṭ", "Letters"),
+    letter("𐤉", "PHOENICIAN LETTER YOD", "y", "Letters"),
+    letter("𐤊", "PHOENICIAN LETTER KAF", "k", "Letters"),
+    letter("𐤋", "PHOENICIAN LETTER LAMD", "l", "Letters"),
+    letter("𐤌", "PHOENICIAN LETTER MEM", "m", "Letters"),
+    letter("𐤍", "PHOENICIAN LETTER NUN", "n", "Letters"),
+    letter("𐤎", "PHOENICIAN LETTER SEMK", "s", "Letters"),
+    letter("𐤏", "PHOENICIAN LETTER AIN", "ʿ", "Letters"),
+    letter("𐤐", "PHOENICIAN LETTER PE", "p", "Letters"),
+    letter("𐤑", "PHOENICIAN LETTER SADE", "ṣ", "Letters"),
+    letter("𐤒", "PHOENICIAN LETTER QOF", "q", "Letters"),
+    letter("𐤓", "PHOENICIAN LETTER ROSH", "r", "Letters"),
+    letter("𐤔", "PHOENICIAN LETTER SHIN", "š", "Letters"),
+    letter("𐤕", "PHOENICIAN LETTER TAU", "t", "Letters"),
+];
+
+const GREEK_LETTERS: &[PresetLetter] = &[
+    letter("Ͱ", "GREEK CAPITAL LETTER HETA", "H", "Archaic"),
+    letter("ͱ", "GREEK SMALL LETTER HETA", "h", "Archaic"),
+    letter("Ͳ", "GREEK CAPITAL LETTER ARCHAIC SAMPI", "Ss", "Archaic"),
+    letter("ͳ", "GREEK SMALL LETTER ARCHAIC SAMPI", "ss", "Archaic"),
+    letter(
+        "Ͷ",
+        "GREEK CAPITAL LETTER PAMPHYLIAN DIGAMMA",
+        "W",
+        "Archaic",
+    ),
+    letter("ͷ", "GREEK SMALL LETTER PAMPHYLIAN DIGAMMA", "w", "Archaic"),
+    letter(
+        "ͻ",
+        "GREEK SMALL REVERSED LUNATE SIGMA SYMBOL",
+        "s",
+        "Symbol variants",
+    ),
+    letter(
+        "ͼ",
+        "GREEK SMALL DOTTED LUNATE SIGMA SYMBOL",
+        "s",
+        "Symbol variants",
+    ),
+    letter(
+        "ͽ",
+        "GREEK SMALL REVERSED DOTTED LUNATE SIGMA SYMBOL",
+        "s",
+        "Symbol variants",
+    ),
+    letter("Ϳ", "GREEK CAPITAL LETTER YOT", "J", "Archaic"),
+    letter(
+        "Ά",
+        "GREEK CAPITAL LETTER ALPHA WITH TONOS",
+        "A",
+        "Accented",
+    ),
+    letter(
+        "Έ",
+        "GREEK CAPITAL LETTER EPSILON WITH TONOS",
+        "E",
+        "Accented",
+    ),
+    letter("Ή", "GREEK CAPITAL LETTER ETA WITH TONOS", "Ē", "Accented"),
+    letter("Ί", "GREEK CAPITAL LETTER IOTA WITH TONOS", "I", "Accented"),
+    letter(
+        "Ό",
+        "GREEK CAPITAL LETTER OMICRON WITH TONOS",
+        "O",
+        "Accented",
+    ),
+    letter(
+        "Ύ",
+        "GREEK CAPITAL LETTER UPSILON WITH TONOS",
+        "Y",
+        "Accented",
+    ),
+    letter(
+        "Ώ",
+        "GREEK CAPITAL LETTER OMEGA WITH TONOS",
+        "Ō",
+        "Accented",
+    ),
+    letter(
+        "ΐ",
+        "GREEK SMALL LETTER IOTA WITH DIALYTIKA AND TONOS",
+        "i",
+        "Accented",
+    ),
+    letter("Α", "GREEK CAPITAL LETTER ALPHA", "A", "Uppercase"),
+    letter("Β", "GREEK CAPITAL LETTER BETA", "B", "Uppercase"),
+    letter("Γ", "GREEK CAPITAL LETTER GAMMA", "G", "Uppercase"),
+    letter("Δ", "GREEK CAPITAL LETTER DELTA", "D", "Uppercase"),
+    letter("Ε", "GREEK CAPITAL LETTER EPSILON", "E", "Uppercase"),
+    letter("Ζ", "GREEK CAPITAL LETTER ZETA", "Z", "Uppercase"),
+    letter("Η", "GREEK CAPITAL LETTER ETA", "Ē", "Uppercase"),
+    letter("Θ", "GREEK CAPITAL LETTER THETA", "Th", "Uppercase"),
+    letter("Ι", "GREEK CAPITAL LETTER IOTA", "I", "Uppercase"),
+    letter("Κ", "GREEK CAPITAL LETTER KAPPA", "K", "Uppercase"),
+    letter("Λ", "GREEK CAPITAL LETTER LAMDA", "L", "Uppercase"),
+    letter("Μ", "GREEK CAPITAL LETTER MU", "M", "Uppercase"),
+    letter("Ν", "GREEK CAPITAL LETTER NU", "N", "Uppercase"),
+    letter("Ξ", "GREEK CAPITAL LETTER XI", "X", "Uppercase"),
+    letter("Ο", "GREEK CAPITAL LETTER OMICRON", "O", "Uppercase"),
+    letter("Π", "GREEK CAPITAL LETTER PI", "P", "Uppercase"),
+    letter("Ρ", "GREEK CAPITAL LETTER RHO", "R", "Uppercase"),
+    letter("Σ", "GREEK CAPITAL LETTER SIGMA", "S", "Uppercase"),
+    letter("Τ", "GREEK CAPITAL LETTER TAU", "T", "Uppercase"),
+    letter("Υ", "GREEK CAPITAL LETTER UPSILON", "Y", "Uppercase"),
+    letter("Φ", "GREEK CAPITAL LETTER PHI", "Ph", "Uppercase"),
+    letter("Χ", "GREEK CAPITAL LETTER CHI", "Ch", "Uppercase"),
+    letter("Ψ", "GREEK CAPITAL LETTER PSI", "Ps", "Uppercase"),
+    letter("Ω", "GREEK CAPITAL LETTER OMEGA", "Ō", "Uppercase"),
+    letter(
+        "Ϊ",
+        "GREEK CAPITAL LETTER IOTA WITH DIALYTIKA",
+        "I",
+        "Accented",
+    ),
+    letter(
+        "Ϋ",
+        "GREEK CAPITAL LETTER UPSILON WITH DIALYTIKA",
+        "Y",
+        "Accented",
+    ),
+    letter("ά", "GREEK SMALL LETTER ALPHA WITH TONOS", "a", "Accented"),
+    letter(
+        "έ",
+        "GREEK SMALL LETTER EPSILON WITH TONOS",
+        "e",
+        "Accented",
+    ),
+    letter("ή", "GREEK SMALL LETTER ETA WITH TONOS", "ē", "Accented"),
+    letter("ί", "GREEK SMALL LETTER IOTA WITH TONOS", "i", "Accented"),
+    letter(
+        "ΰ",
+        "GREEK SMALL LETTER UPSILON WITH DIALYTIKA AND TONOS",
+        "y",
+        "Accented",
+    ),
+    letter("α", "GREEK SMALL LETTER ALPHA", "a", "Lowercase"),
+    letter("β", "GREEK SMALL LETTER BETA", "b", "Lowercase"),
+    letter("γ", "GREEK SMALL LETTER GAMMA", "g", "Lowercase"),
+    letter("δ", "GREEK SMALL LETTER DELTA", "d", "Lowercase"),
+    letter("ε", "GREEK SMALL LETTER EPSILON", "e", "Lowercase"),
+    letter("ζ", "GREEK SMALL LETTER ZETA", "z", "Lowercase"),
+    letter("η", "GREEK SMALL LETTER ETA", "ē", "Lowercase"),
+    letter("θ", "GREEK SMALL LETTER THETA", "th", "Lowercase"),
+    letter("ι", "GREEK SMALL LETTER IOTA", "i", "Lowercase"),
+    letter("κ", "GREEK SMALL LETTER KAPPA", "k", "Lowercase"),
+    letter("λ", "GREEK SMALL LETTER LAMDA", "l", "Lowercase"),
+    letter("μ", "GREEK SMALL LETTER MU", "m", "Lowercase"),
+    letter("ν", "GREEK SMALL LETTER NU", "n", "Lowercase"),
+    letter("ξ", "GREEK SMALL LETTER XI", "x", "Lowercase"),
+    letter("ο", "GREEK SMALL LETTER OMICRON", "o", "Lowercase"),
+    letter("π", "GREEK SMALL LETTER PI", "p", "Lowercase"),
+    letter("ρ", "GREEK SMALL LETTER RHO", "r", "Lowercase"),
+    letter("ς", "GREEK SMALL LETTER FINAL SIGMA", "s", "Lowercase"),
+    letter("σ", "GREEK SMALL LETTER SIGMA", "s", "Lowercase"),
+    letter("τ", "GREEK SMALL LETTER TAU", "t", "Lowercase"),
+    letter("υ", "GREEK SMALL LETTER UPSILON", "y", "Lowercase"),
+    letter("φ", "GREEK SMALL LETTER PHI", "ph", "Lowercase"),
+    letter("χ", "GREEK SMALL LETTER CHI", "ch", "Lowercase"),
+    letter("ψ", "GREEK SMALL LETTER PSI", "ps", "Lowercase"),
+    letter("ω", "GREEK SMALL LETTER OMEGA", "ō", "Lowercase"),
+    letter(
+        "ϊ",
+        "GREEK SMALL LETTER IOTA WITH DIALYTIKA",
+        "i",
+        "Accented",
+    ),
+    letter(
+        "ϋ",
+        "GREEK SMALL LETTER UPSILON WITH DIALYTIKA",
+        "y",
+        "Accented",
+    ),
+    letter(
+        "ό",
+        "GREEK SMALL LETTER OMICRON WITH TONOS",
+        "o",
+        "Accented",
+    ),
+    letter(
+        "ύ",
+        "GREEK SMALL LETTER UPSILON WITH TONOS",
+        "y",
+        "Accented",
+    ),
+    letter("ώ", "GREEK SMALL LETTER OMEGA WITH TONOS", "ō", "Accented"),
+    letter("Ϗ", "GREEK CAPITAL KAI SYMBOL", "Kai", "Symbol variants"),
+    letter("ϐ", "GREEK BETA SYMBOL", "b", "Symbol variants"),
+    letter("ϑ", "GREEK THETA SYMBOL", "th", "Symbol variants"),
+    letter(
+        "ϒ",
+        "GREEK UPSILON WITH HOOK SYMBOL",
+        "Y",
+        "Symbol variants",
+    ),
+    letter(
+        "ϓ",
+        "GREEK UPSILON WITH ACUTE AND HOOK SYMBOL",
+        "Y",
+        "Symbol variants",
+    ),
+    letter(
+        "ϔ",
+        "GREEK UPSILON WITH DIAERESIS AND HOOK SYMBOL",
+        "Y",
+        "Symbol variants",
+    ),
+    letter("ϕ", "GREEK PHI SYMBOL", "ph", "Symbol variants"),
+    letter("ϖ", "GREEK PI SYMBOL", "p", "Symbol variants"),
+    letter("ϗ", "GREEK KAI SYMBOL", "kai", "Symbol variants"),
+    letter("Ϙ", "GREEK LETTER ARCHAIC KOPPA", "Q", "Archaic"),
+    letter("ϙ", "GREEK SMALL LETTER ARCHAIC KOPPA", "q", "Archaic"),
+    letter("Ϛ", "GREEK LETTER STIGMA", "St", "Archaic"),
+    letter("ϛ", "GREEK SMALL LETTER STIGMA", "st", "Archaic"),
+    letter("Ϝ", "GREEK LETTER DIGAMMA", "W", "Archaic"),
+    letter("ϝ", "GREEK SMALL LETTER DIGAMMA", "w", "Archaic"),
+    letter("Ϟ", "GREEK LETTER KOPPA", "Q", "Archaic"),
+    letter("ϟ", "GREEK SMALL LETTER KOPPA", "q", "Archaic"),
+    letter("Ϡ", "GREEK LETTER SAMPI", "Ss", "Archaic"),
+    letter("ϡ", "GREEK SMALL LETTER SAMPI", "ss", "Archaic"),
+    letter("ϰ", "GREEK KAPPA SYMBOL", "k", "Symbol variants"),
+    letter("ϱ", "GREEK RHO SYMBOL", "r", "Symbol variants"),
+    letter("ϲ", "GREEK LUNATE SIGMA SYMBOL", "s", "Symbol variants"),
+    letter("ϳ", "GREEK LETTER YOT", "j", "Archaic"),
+    letter("ϴ", "GREEK CAPITAL THETA SYMBOL", "Th", "Symbol variants"),
+    letter("ϵ", "GREEK LUNATE EPSILON SYMBOL", "e", "Symbol variants"),
+    letter("Ϸ", "GREEK CAPITAL LETTER SHO", "Š", "Archaic"),
+    letter("ϸ", "GREEK SMALL LETTER SHO", "š", "Archaic"),
+    letter(
+        "Ϲ",
+        "GREEK CAPITAL LUNATE SIGMA SYMBOL",
+        "S",
+        "Symbol variants",
+    ),
+    letter("Ϻ", "GREEK CAPITAL LETTER SAN", "Ś", "Archaic"),
+    letter("ϻ", "GREEK SMALL LETTER SAN", "ś", "Archaic"),
+    letter("ϼ", "GREEK RHO WITH STROKE SYMBOL", "r", "Symbol variants"),
+    letter(
+        "Ͻ",
+        "GREEK CAPITAL REVERSED LUNATE SIGMA SYMBOL",
+        "S",
+        "Symbol variants",
+    ),
+    letter(
+        "Ͼ",
+        "GREEK CAPITAL DOTTED LUNATE SIGMA SYMBOL",
+        "S",
+        "Symbol variants",
+    ),
+    letter(
+        "Ͽ",
+        "GREEK CAPITAL REVERSED DOTTED LUNATE SIGMA SYMBOL",
+        "S",
+        "Symbol variants",
+    ),
+];
+
+const RUNIC_LETTERS: &[PresetLetter] = &[
+    letter("ᚠ", "RUNIC LETTER FEHU FEOH FE F", "f", "Elder Futhark"),
+    letter("ᚡ", "RUNIC LETTER V", "v", "Other runes"),
+    letter("ᚢ", "RUNIC LETTER URUZ UR U", "u", "Elder Futhark"),
+    letter("ᚣ", "RUNIC LETTER YR", "yr", "Other runes"),
+    letter("ᚤ", "RUNIC LETTER Y", "y", "Other runes"),
+    letter("ᚥ", "RUNIC LETTER W", "w", "Other runes"),
+    letter(
+        "ᚦ",
+        "RUNIC LETTER THURISAZ THURS THORN",
+        "þ",
+        "Elder Futhark",
+    ),
+    letter("ᚧ", "RUNIC LETTER ETH", "", "Other runes"),
+    letter("ᚨ", "RUNIC LETTER ANSUZ A", "a", "Elder Futhark"),
+    letter("ᚩ", "RUNIC LETTER OS O", "o", "Other runes"),
+    letter("ᚪ", "RUNIC LETTER AC A", "a", "Other runes"),
+    letter("ᚫ", "RUNIC LETTER AESC", "", "Other runes"),
+    letter("ᚬ", "RUNIC LETTER LONG-BRANCH-OSS O", "o", "Other runes"),
+    letter("ᚭ", "RUNIC LETTER SHORT-TWIG-OSS O", "o", "Other runes"),
+    letter("ᚮ", "RUNIC LETTER O", "o", "Other runes"),
+    letter("ᚯ", "RUNIC LETTER OE", "oe", "Other runes"),
+    letter("ᚰ", "RUNIC LETTER ON", "on", "Other runes"),
+    letter("ᚱ", "RUNIC LETTER RAIDO RAD REID R", "r", "Elder Futhark"),
+    letter("ᚲ", "RUNIC LETTER KAUNA", "k", "Elder Futhark"),
+    letter("ᚳ", "RUNIC LETTER CEN", "", "Other runes"),
+    letter("ᚴ", "RUNIC LETTER KAUN K", "k", "Other runes"),
+    letter("ᚵ", "RUNIC LETTER G", "g", "Other runes"),
+    letter("ᚶ", "RUNIC LETTER ENG", "", "Other runes"),
+    letter("ᚷ", "RUNIC LETTER GEBO GYFU G", "g", "Elder Futhark"),
+    letter("ᚸ", "RUNIC LETTER GAR", "", "Other runes"),
+    letter("ᚹ", "RUNIC LETTER WUNJO WYNN W", "w", "Elder Futhark"),
+    letter("ᚺ", "RUNIC LETTER HAGLAZ H", "h", "Elder Futhark"),
+    letter("ᚻ", "RUNIC LETTER HAEGL H", "h", "Other runes"),
+    letter("ᚼ", "RUNIC LETTER LONG-BRANCH-HAGALL H", "h", "Other runes"),
+    letter("ᚽ", "RUNIC LETTER SHORT-TWIG-HAGALL H", "h", "Other runes"),
+    letter("ᚾ", "RUNIC LETTER NAUDIZ NYD NAUD N", "n", "Elder Futhark"),
+    letter("ᚿ", "RUNIC LETTER SHORT-TWIG-NAUD N", "n", "Other runes"),
+    letter("ᛀ", "RUNIC LETTER DOTTED-N", "", "Other runes"),
+    letter("ᛁ", "RUNIC LETTER ISAZ IS ISS I", "i", "Elder Futhark"),
+    letter("ᛂ", "RUNIC LETTER E", "e", "Other runes"),
+    letter("ᛃ", "RUNIC LETTER JERAN J", "j", "Elder Futhark"),
+    letter("ᛄ", "RUNIC LETTER GER", "", "Other runes"),
+    letter("ᛅ", "RUNIC LETTER LONG-BRANCH-AR AE", "ae", "Other runes"),
+    letter("ᛆ", "RUNIC LETTER SHORT-TWIG-AR A", "a", "Other runes"),
+    letter("ᛇ", "RUNIC LETTER IWAZ EOH", "ï", "Elder Futhark"),
+    letter("ᛈ", "RUNIC LETTER PERTHO PEORTH P", "p", "Elder Futhark"),
+    letter("ᛉ", "RUNIC LETTER ALGIZ EOLHX", "z", "Elder Futhark"),
+    letter("ᛊ", "RUNIC LETTER SOWILO S", "s", "Elder Futhark"),
+    letter(
+        "ᛋ",
+        "RUNIC LETTER SIGEL LONG-BRANCH-SOL S",
+        "s",
+        "Other runes",
+    ),
+    letter("ᛌ", "RUNIC LETTER SHORT-TWIG-SOL S", "s", "Other runes"),
+    letter("ᛍ", "RUNIC LETTER C", "c", "Other runes"),
+    letter("ᛎ", "RUNIC LETTER Z", "z", "Other runes"),
+    letter("ᛏ", "RUNIC LETTER TIWAZ TIR TYR T", "t", "Elder Futhark"),
+    letter("ᛐ", "RUNIC LETTER SHORT-TWIG-TYR T", "t", "Other runes"),
+    letter("ᛑ", "RUNIC LETTER D", "d", "Other runes"),
+    letter(
+        "ᛒ",
+        "RUNIC LETTER BERKANAN BEORC BJARKAN B",
+        "b",
+        "Elder Futhark",
+    ),
+    letter("ᛓ", "RUNIC LETTER SHORT-TWIG-BJARKAN B", "b", "Other runes"),
+    letter("ᛔ", "RUNIC LETTER DOTTED-P", "", "Other runes"),
+    letter("ᛕ", "RUNIC LETTER OPEN-P", "", "Other runes"),
+    letter("ᛖ", "RUNIC LETTER EHWAZ EH E", "e", "Elder Futhark"),
+    letter("ᛗ", "RUNIC LETTER MANNAZ MAN M", "m", "Elder Futhark"),
+    letter("ᛘ", "RUNIC LETTER LONG-BRANCH-MADR M", "m", "Other runes"),
+    letter("ᛙ", "RUNIC LETTER SHORT-TWIG-MADR M", "m", "Other runes"),
+    letter("ᛚ", "RUNIC LETTER LAUKAZ LAGU LOGR L", "l", "Elder Futhark"),
+    letter("ᛛ", "RUNIC LETTER DOTTED-L", "", "Other runes"),
+    letter("ᛜ", "RUNIC LETTER INGWAZ", "ŋ", "Elder Futhark"),
+    letter("ᛝ", "RUNIC LETTER ING", "", "Other runes"),
+    letter("ᛞ", "RUNIC LETTER DAGAZ DAEG D", "d", "Elder Futhark"),
+    letter("ᛟ", "RUNIC LETTER OTHALAN ETHEL O", "o", "Elder Futhark"),
+    letter("ᛠ", "RUNIC LETTER EAR", "", "Other runes"),
+    letter("ᛡ", "RUNIC LETTER IOR", "", "Other runes"),
+    letter("ᛢ", "RUNIC LETTER CWEORTH", "", "Other runes"),
+    letter("ᛣ", "RUNIC LETTER CALC", "", "Other runes"),
+    letter("ᛤ", "RUNIC LETTER CEALC", "", "Other runes"),
+    letter("ᛥ", "RUNIC LETTER STAN", "", "Other runes"),
+    letter("ᛦ", "RUNIC LETTER LONG-BRANCH-YR", "", "Other runes"),
+    letter("ᛧ", "RUNIC LETTER SHORT-TWIG-YR", "", "Other runes"),
+    letter("ᛨ", "RUNIC LETTER ICELANDIC-YR", "", "Other runes"),
+    letter("ᛩ", "RUNIC LETTER Q", "q", "Other runes"),
+    letter("ᛪ", "RUNIC LETTER X", "x", "Other runes"),
+    letter("ᛱ", "RUNIC LETTER K", "k", "Other runes"),
+    letter("ᛲ", "RUNIC LETTER SH", "sh", "Other runes"),
+    letter("ᛳ", "RUNIC LETTER OO", "oo", "Other runes"),
+    letter("ᛴ", "RUNIC LETTER FRANKS CASKET OS", "", "Other runes"),
+    letter("ᛵ", "RUNIC LETTER FRANKS CASKET IS", "", "Other runes"),
+    letter("ᛶ", "RUNIC LETTER FRANKS CASKET EH", "", "Other runes"),
+    letter("ᛷ", "RUNIC LETTER FRANKS CASKET AC", "", "Other runes"),
+    letter("ᛸ", "RUNIC LETTER FRANKS CASKET AESC", "", "Other runes"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    #[test]
+    fn every_letter_is_a_single_grapheme() {
+        for preset in PRESETS {
+            for letter in preset.letters {
+                assert_eq!(
+                    letter.symbol.graphemes(true).count(),
+                    1,
+                    "{}",
+                    letter.unicode_name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn letters_are_unique() {
+        for preset in PRESETS {
+            assert!(preset
+                .letters
+                .iter()
+                .map(|letter| letter.symbol)
+                .all_unique());
+        }
+    }
+
+    #[test]
+    fn elder_futhark_has_24_runes() {
+        let runic = find_preset("runic").unwrap();
+        let selection = PresetSelection::Groups(vec!["Elder Futhark".to_owned()]);
+        assert_eq!(runic.select(&selection).unwrap().len(), 24);
+    }
+
+    #[test]
+    fn phoenician_has_22_letters() {
+        let phoenician = find_preset("phoenician").unwrap();
+        assert_eq!(phoenician.select(&PresetSelection::All).unwrap().len(), 22);
+    }
+
+    #[test]
+    fn select_letters() {
+        let greek = find_preset("greek").unwrap();
+        let selection = PresetSelection::Letters(vec!["α".to_owned(), "Ω".to_owned()]);
+        let selected = greek.select(&selection).unwrap();
+        assert_eq!(
+            selected
+                .iter()
+                .map(|letter| letter.transliteration)
+                .collect_vec(),
+            vec!["Ō", "a"]
+        );
+    }
+
+    #[test]
+    fn select_unknown_letter() {
+        let greek = find_preset("greek").unwrap();
+        let selection = PresetSelection::Letters(vec!["ᚠ".to_owned()]);
+        assert!(greek.select(&selection).is_err());
+    }
+
+    #[test]
+    fn select_unknown_group() {
+        let greek = find_preset("greek").unwrap();
+        let selection = PresetSelection::Groups(vec!["Cyrillic".to_owned()]);
+        assert!(greek.select(&selection).is_err());
+    }
+}
@@ -0,0 +1,479 @@
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+
+/// The rules a generated word must follow.
+///
+/// The rules are stored in the data directory, so every language (data directory) can have its own set.
+/// All fields are optional in the JSON file. Missing fields fall back to the rules from the README.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WordRules {
+    /// The maximum amount of times the same letter may appear consecutively. `None` means no limit.
+    pub max_consecutive_repeats: Option<usize>,
+    /// Substrings that must not appear anywhere in a word. Forbidden bigrams are simply two letter substrings.
+    pub forbidden_substrings: Vec<String>,
+    /// Every class must be represented by at least one of its letters in each word.
+    pub required_glyph_classes: Vec<GlyphClass>,
+    /// If not empty, a word must start with one of these letters.
+    pub must_start_with: Vec<String>,
+    /// If not empty, a word must end with one of these letters.
+    pub must_end_with: Vec<String>,
+    pub regex_constraints: Vec<RegexConstraint>,
+    pub length: LengthRules,
+}
+
+impl Default for WordRules {
+    fn default() -> Self {
+        WordRules {
+            max_consecutive_repeats: Some(2),
+            forbidden_substrings: Vec::new(),
+            required_glyph_classes: Vec::new(),
+            must_start_with: Vec::new(),
+            must_end_with: Vec::new(),
+            regex_constraints: Vec::new(),
+            length: LengthRules::default(),
+        }
+    }
+}
+
+/// How the length of a generated word relates to the length of its English counterpart.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct LengthRules {
+    /// The shortest word is this ratio of the English length, rounded down.
+    pub min_ratio: f64,
+    /// The longest word is this ratio of the English length, rounded up.
+    pub max_ratio: f64,
+    /// No word is ever shorter than this.
+    pub min_length: usize,
+    /// No word is ever longer than this. `None` means no limit.
+    pub max_length: Option<usize>,
+    pub distribution: LengthDistribution,
+}
+
+impl Default for LengthRules {
+    fn default() -> Self {
+        LengthRules {
+            min_ratio: 0.5,
+            max_ratio: 2.0,
+            min_length: 1,
+            max_length: None,
+            distribution: LengthDistribution::Uniform,
+        }
+    }
+}
+
+/// How a length is picked from the range allowed by the [`LengthRules`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LengthDistribution {
+    /// Every length in the range is equally likely.
+    Uniform,
+    /// The English length is the most likely, the likelihood drops linearly towards the ends of the range.
+    Triangular,
+    /// Every length gets the weight of the entry matching its difference with the English length.
+    /// Lengths without an entry are never picked.
+    Table { weights: Vec<LengthWeight> },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LengthWeight {
+    /// The generated length minus the English length.
+    pub difference: i64,
+    pub weight: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GlyphClass {
+    pub name: String,
+    pub glyphs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegexConstraint {
+    pub pattern: String,
+    /// When `true`, a word must match the pattern. When `false`, a word must not match the pattern.
+    pub must_match: bool,
+}
+
+impl WordRules {
+    /// Checks a word against all rules and returns the first rule it breaks.
+    ///
+    /// # Arguments
+    ///
+    /// * `letters` - The word, split into the letters it consists of.
+    pub fn check(&self, letters: &[&str]) -> Result<(), RuleViolation> {
+        self.compile()?.check(letters)
+    }
+
+    /// Prepares the rules for checking many words, so the regexes only need to be compiled once.
+    pub fn compile(&self) -> Result<CompiledWordRules<'_>, RuleViolation> {
+        let regexes = self
+            .regex_constraints
+            .iter()
+            .map(|constraint| {
+                Regex::new(&constraint.pattern)
+                    .map(|regex| (regex, constraint))
+                    .map_err(|inner| RuleViolation::InvalidRegex {
+                        pattern: constraint.pattern.clone(),
+                        reason: inner.to_string(),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CompiledWordRules {
+            rules: self,
+            regexes,
+        })
+    }
+}
+
+pub struct CompiledWordRules<'a> {
+    rules: &'a WordRules,
+    regexes: Vec<(Regex, &'a RegexConstraint)>,
+}
+
+impl CompiledWordRules<'_> {
+    /// Checks a complete word against all rules and returns the first rule it breaks.
+    pub fn check(&self, letters: &[&str]) -> Result<(), RuleViolation> {
+        if let Some(max) = self.rules.max_consecutive_repeats {
+            check_consecutive_repeats(letters, max)?;
+        }
+
+        let word = letters.concat();
+        check_forbidden_substrings(&word, &self.rules.forbidden_substrings)?;
+
+        if let Some(class) = self.rules.required_glyph_classes.iter().find(|class| {
+            !letters
+                .iter()
+                .any(|letter| class.glyphs.iter().any(|g| g == letter))
+        }) {
+            return Err(RuleViolation::MissingGlyphClass(class.name.clone()));
+        }
+
+        if let Some(first) = letters.first() {
+            self.check_start(first)?;
+        }
+
+        if let Some(last) = letters.last() {
+            self.check_end(last)?;
+        }
+
+        for (regex, constraint) in &self.regexes {
+            match (constraint.must_match, regex.is_match(&word)) {
+                (true, false) => {
+                    return Err(RuleViolation::RegexMismatch(constraint.pattern.clone()))
+                }
+                (false, true) => {
+                    return Err(RuleViolation::RegexForbiddenMatch(
+                        constraint.pattern.clone(),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether `next` may follow the `prefix` in a word of `length` letters.
+    ///
+    /// Only the rules that can be decided letter by letter are checked.
+    /// The `prefix` is assumed to already pass these rules.
+    /// Use [`CompiledWordRules::check`] on the finished word for the remaining rules.
+    pub fn check_next(
+        &self,
+        prefix: &[&str],
+        next: &str,
+        length: usize,
+    ) -> Result<(), RuleViolation> {
+        if let Some(max) = self.rules.max_consecutive_repeats {
+            let repeats = prefix
+                .iter()
+                .rev()
+                .take_while(|letter| **letter == next)
+                .count()
+                + 1;
+            if repeats > max {
+                return Err(RuleViolation::TooManyRepeats {
+                    letter: next.to_owned(),
+                    max,
+                });
+            }
+        }
+
+        let word = prefix.concat() + next;
+        check_forbidden_substrings(&word, &self.rules.forbidden_substrings)?;
+
+        if prefix.is_empty() {
+            self.check_start(next)?;
+        }
+
+        if prefix.len() + 1 == length {
+            self.check_end(next)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_start(&self, first: &str) -> Result<(), RuleViolation> {
+        let must_start_with = &self.rules.must_start_with;
+        if !must_start_with.is_empty() && !must_start_with.iter().any(|g| g == first) {
+            return Err(RuleViolation::InvalidStart(first.to_owned()));
+        }
+        Ok(())
+    }
+
+    fn check_end(&self, last: &str) -> Result<(), RuleViolation> {
+        let must_end_with = &self.rules.must_end_with;
+        if !must_end_with.is_empty() && !must_end_with.iter().any(|g| g == last) {
+            return Err(RuleViolation::InvalidEnd(last.to_owned()));
+        }
+        Ok(())
+    }
+}
+
+fn check_consecutive_repeats(letters: &[&str], max: usize) -> Result<(), RuleViolation> {
+    let mut count = 0;
+    for (index, letter) in letters.iter().enumerate() {
+        if index > 0 && letters[index - 1] == *letter {
+            count += 1;
+        } else {
+            count = 1;
+        }
+
+        if count > max {
+            return Err(RuleViolation::TooManyRepeats {
+                letter: letter.to_string(),
+                max,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn check_forbidden_substrings(
+    word: &str,
+    forbidden_substrings: &[String],
+) -> Result<(), RuleViolation> {
+    match forbidden_substrings
+        .iter()
+        .find(|forbidden| !forbidden.is_empty() && word.contains(forbidden.as_str()))
+    {
+        Some(forbidden) => Err(RuleViolation::ForbiddenSubstring(forbidden.clone())),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RuleViolation {
+    TooManyRepeats { letter: String, max: usize },
+    ForbiddenSubstring(String),
+    MissingGlyphClass(String),
+    InvalidStart(String),
+    InvalidEnd(String),
+    RegexMismatch(String),
+    RegexForbiddenMatch(String),
+    InvalidRegex { pattern: String, reason: String },
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleViolation::TooManyRepeats { letter, max } => write!(
+                f,
+                "the letter '{letter}' appears more than {max} times consecutively"
+            ),
+            RuleViolation::ForbiddenSubstring(forbidden) => {
+                write!(f, "the word contains the forbidden sequence '{forbidden}'")
+            }
+            RuleViolation::MissingGlyphClass(class) => {
+                write!(f, "the word contains no letter of the class '{class}'")
+            }
+            RuleViolation::InvalidStart(letter) => {
+                write!(f, "a word is not allowed to start with '{letter}'")
+            }
+            RuleViolation::InvalidEnd(letter) => {
+                write!(f, "a word is not allowed to end with '{letter}'")
+            }
+            RuleViolation::RegexMismatch(pattern) => {
+                write!(f, "the word does not match the pattern '{pattern}'")
+            }
+            RuleViolation::RegexForbiddenMatch(pattern) => {
+                write!(f, "the word matches the forbidden pattern '{pattern}'")
+            }
+            RuleViolation::InvalidRegex { pattern, reason } => {
+                write!(f, "the pattern '{pattern}' is not a valid regex: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for RuleViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters(word: &str) -> Vec<&str> {
+        unicode_segmentation::UnicodeSegmentation::graphemes(word, true).collect()
+    }
+
+    #[test]
+    fn triple_char_is_not_valid_word() {
+        let rules = WordRules::default();
+        assert!(rules.check(&letters("aaa")).is_err())
+    }
+
+    #[test]
+    fn double_char_is_valid_word() {
+        let rules = WordRules::default();
+        assert!(rules.check(&letters("aa")).is_ok())
+    }
+
+    #[test]
+    fn triple_char_with_other_char_in_between_is_valid_word() {
+        let rules = WordRules::default();
+        assert!(rules.check(&letters("aabaa")).is_ok())
+    }
+
+    #[test]
+    fn unlimited_repeats() {
+        let rules = WordRules {
+            max_consecutive_repeats: None,
+            ..Default::default()
+        };
+        assert!(rules.check(&letters("aaaa")).is_ok())
+    }
+
+    #[test]
+    fn reject_forbidden_bigram() {
+        let rules = WordRules {
+            forbidden_substrings: vec!["ab".to_owned()],
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.check(&letters("cabc")),
+            Err(RuleViolation::ForbiddenSubstring("ab".to_owned()))
+        );
+        assert!(rules.check(&letters("cbac")).is_ok());
+    }
+
+    #[test]
+    fn require_glyph_class() {
+        let rules = WordRules {
+            required_glyph_classes: vec![GlyphClass {
+                name: "vowels".to_owned(),
+                glyphs: vec!["a".to_owned(), "e".to_owned()],
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.check(&letters("xyz")),
+            Err(RuleViolation::MissingGlyphClass("vowels".to_owned()))
+        );
+        assert!(rules.check(&letters("xez")).is_ok());
+    }
+
+    #[test]
+    fn start_and_end() {
+        let rules = WordRules {
+            must_start_with: vec!["x".to_owned()],
+            must_end_with: vec!["z".to_owned()],
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.check(&letters("ayz")),
+            Err(RuleViolation::InvalidStart("a".to_owned()))
+        );
+        assert_eq!(
+            rules.check(&letters("xya")),
+            Err(RuleViolation::InvalidEnd("a".to_owned()))
+        );
+        assert!(rules.check(&letters("xyz")).is_ok());
+    }
+
+    #[test]
+    fn regex_constraints() {
+        let rules = WordRules {
+            regex_constraints: vec![
+                RegexConstraint {
+                    pattern: "^.{2,}$".to_owned(),
+                    must_match: true,
+                },
+                RegexConstraint {
+                    pattern: "q$".to_owned(),
+                    must_match: false,
+                },
+            ],
+            ..Default::default()
+        };
+        assert!(matches!(
+            rules.check(&letters("a")),
+            Err(RuleViolation::RegexMismatch(_))
+        ));
+        assert!(matches!(
+            rules.check(&letters("aq")),
+            Err(RuleViolation::RegexForbiddenMatch(_))
+        ));
+        assert!(rules.check(&letters("ab")).is_ok());
+    }
+
+    #[test]
+    fn invalid_regex_is_reported() {
+        let rules = WordRules {
+            regex_constraints: vec![RegexConstraint {
+                pattern: "(".to_owned(),
+                must_match: true,
+            }],
+            ..Default::default()
+        };
+        assert!(matches!(
+            rules.check(&letters("a")),
+            Err(RuleViolation::InvalidRegex { .. })
+        ));
+    }
+
+    #[test]
+    fn next_letter_respects_repeats() {
+        let rules = WordRules::default();
+        let compiled = rules.compile().unwrap();
+        assert!(compiled.check_next(&["a", "a"], "a", 5).is_err());
+        assert!(compiled.check_next(&["a", "a"], "b", 5).is_ok());
+    }
+
+    #[test]
+    fn next_letter_respects_forbidden_substrings() {
+        let rules = WordRules {
+            forbidden_substrings: vec!["ab".to_owned()],
+            ..Default::default()
+        };
+        let compiled = rules.compile().unwrap();
+        assert!(compiled.check_next(&["c", "a"], "b", 5).is_err());
+        assert!(compiled.check_next(&["c", "b"], "a", 5).is_ok());
+    }
+
+    #[test]
+    fn next_letter_respects_start_and_end() {
+        let rules = WordRules {
+            must_start_with: vec!["x".to_owned()],
+            must_end_with: vec!["z".to_owned()],
+            ..Default::default()
+        };
+        let compiled = rules.compile().unwrap();
+        assert!(compiled.check_next(&[], "a", 3).is_err());
+        assert!(compiled.check_next(&[], "x", 3).is_ok());
+        assert!(compiled.check_next(&["x"], "a", 3).is_ok());
+        assert!(compiled.check_next(&["x", "a"], "a", 3).is_err());
+        assert!(compiled.check_next(&["x", "a"], "z", 3).is_ok());
+    }
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let rules: WordRules = serde_json::from_str("{}").unwrap();
+        assert_eq!(rules, WordRules::default());
+    }
+}
@@ -8,7 +8,17 @@ use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::BufReader;
 use std::io::BufWriter;
+use std::path::Path;
+use std::time::SystemTime;
 
+use serde::Deserialize;
+
+use super::block_list::BlockList;
+use super::block_list::BlockPattern;
+use super::block_list::BlockedTranslation;
+use super::journal::Journal;
+use super::knowledge::Knowledge;
+use super::rules::WordRules;
 use super::Glyph;
 use super::Translation;
 
@@ -18,6 +28,10 @@ const EXTENSION: &str = "json";
 const TRANSLATED_WORDS_FILENAME: &str = "translated_words";
 const ALPHABET_FILENAME: &str = "alphabet";
 const BLOCKED_TRANSLATIONS_FILENAME: &str = "blocked_translations";
+const WORD_RULES_FILENAME: &str = "word_rules";
+const BLOCK_PATTERNS_FILENAME: &str = "blocked_patterns";
+const JOURNAL_FILENAME: &str = "journal";
+const KNOWLEDGE_FILENAME: &str = "party_knowledge";
 
 pub fn load_translated_words() -> HashMap<String, Translation> {
     match load_from_file(TRANSLATED_WORDS_FILENAME) {
@@ -51,26 +65,98 @@ pub async fn load_alphabet_async() -> Vec<Glyph> {
     load_alphabet()
 }
 
-pub fn save_alphabet(alphabet: &Vec<Glyph>) -> Result<(), Box<dyn Error>> {
-    let mut sorted_alphabet = alphabet.clone();
-    sorted_alphabet.sort_unstable_by(|l, r| l.symbol.cmp(&r.symbol));
-    save_to_file(&sorted_alphabet, ALPHABET_FILENAME)
+/// Saves the alphabet. The order of the letters is kept, so the user can arrange them as they like.
+pub fn save_alphabet(alphabet: &[Glyph]) -> Result<(), Box<dyn Error>> {
+    save_to_file(&alphabet, ALPHABET_FILENAME)
 }
 
-pub fn load_blocked_translations() -> Vec<String> {
+pub fn load_blocked_translations() -> BlockList {
     match load_from_file(BLOCKED_TRANSLATIONS_FILENAME) {
-        Ok(blocked) => blocked,
+        Ok(StoredBlockList::Current(blocked)) => blocked,
+        Ok(StoredBlockList::Legacy(blocked)) => blocked
+            .into_iter()
+            .map(|word| {
+                (
+                    word,
+                    BlockedTranslation {
+                        reason: None,
+                        time_added: SystemTime::UNIX_EPOCH,
+                    },
+                )
+            })
+            .collect(),
         Err(error) => {
             error!("{error}");
-            Vec::new()
+            BlockList::new()
         }
     }
 }
 
-pub fn save_blocked_translations(translations: Vec<String>) -> Result<(), Box<dyn Error>> {
-    let mut sorted_translations = translations.clone();
-    sorted_translations.sort_unstable();
-    save_to_file(&sorted_translations, BLOCKED_TRANSLATIONS_FILENAME)
+pub fn save_blocked_translations(translations: &BlockList) -> Result<(), Box<dyn Error>> {
+    save_to_file(translations, BLOCKED_TRANSLATIONS_FILENAME)
+}
+
+/// The block list used to be a plain list of words. Those files can still be read.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredBlockList {
+    Current(BlockList),
+    Legacy(Vec<String>),
+}
+
+pub fn load_block_patterns() -> Vec<BlockPattern> {
+    load_settings_from_file(BLOCK_PATTERNS_FILENAME)
+}
+
+pub fn save_block_patterns(patterns: &[BlockPattern]) -> Result<(), Box<dyn Error>> {
+    save_to_file(&patterns, BLOCK_PATTERNS_FILENAME)
+}
+
+/// Loads the word rules of the language.
+/// If no rules were saved yet, the default rules from the README are used.
+pub fn load_word_rules() -> WordRules {
+    load_settings_from_file(WORD_RULES_FILENAME)
+}
+
+pub fn save_word_rules(rules: &WordRules) -> Result<(), Box<dyn Error>> {
+    save_to_file(rules, WORD_RULES_FILENAME)
+}
+
+/// Loads the changes that can be undone and redone. Empty if nothing was changed yet.
+pub fn load_journal() -> Journal {
+    load_settings_from_file(JOURNAL_FILENAME)
+}
+
+pub fn save_journal(journal: &Journal) -> Result<(), Box<dyn Error>> {
+    save_to_file(journal, JOURNAL_FILENAME)
+}
+
+/// Loads the words revealed to each party. Empty if nothing was revealed yet.
+pub fn load_knowledge() -> Knowledge {
+    load_settings_from_file(KNOWLEDGE_FILENAME)
+}
+
+pub fn save_knowledge(knowledge: &Knowledge) -> Result<(), Box<dyn Error>> {
+    save_to_file(knowledge, KNOWLEDGE_FILENAME)
+}
+
+fn load_settings_from_file<TData>(filename: &str) -> TData
+where
+    TData: for<'de> serde::Deserialize<'de> + Default,
+{
+    if !Path::new(&build_path(filename)).exists()
+        && !Path::new(&build_backup_path(filename)).exists()
+    {
+        return TData::default();
+    }
+
+    match load_from_file(filename) {
+        Ok(settings) => settings,
+        Err(error) => {
+            error!("{error}");
+            TData::default()
+        }
+    }
 }
 
 fn save_to_file<TData>(data: &TData, filename: &str) -> Result<(), Box<dyn Error>>
@@ -127,7 +213,7 @@ fn load_from_file_impl<TData>(path: &str) -> Result<TData, Box<dyn Error>>
 where
     TData: for<'de> serde::Deserialize<'de>,
 {
-    let file = File::open(&path).map_err(|inner| LoadFileError {
+    let file = File::open(path).map_err(|inner| LoadFileError {
         name: path.to_owned(),
         source: inner,
     })?;
@@ -221,3 +307,21 @@ impl<TError: std::error::Error + 'static> Error for SaveFileError<TError> {
         Some(&self.source)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_legacy_block_list() {
+        let stored: StoredBlockList = serde_json::from_str(r#"["foo", "bar"]"#).unwrap();
+        assert!(matches!(stored, StoredBlockList::Legacy(words) if words.len() == 2));
+    }
+
+    #[test]
+    fn read_current_block_list() {
+        let json = r#"{"foo": {"reason": null, "time_added": {"secs_since_epoch": 1, "nanos_since_epoch": 0}}}"#;
+        let stored: StoredBlockList = serde_json::from_str(json).unwrap();
+        assert!(matches!(stored, StoredBlockList::Current(words) if words.contains_key("foo")));
+    }
+}
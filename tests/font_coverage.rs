@@ -0,0 +1,70 @@
+use scrambler::fonts::BUNDLED_FONTS;
+use scrambler::scrambler::presets::find_preset;
+use ttf_parser::Face;
+use ttf_parser::OutlineBuilder;
+
+/// Ignores the outline, only used to check that a glyph has one.
+struct NoOutline;
+
+impl OutlineBuilder for NoOutline {
+    fn move_to(&mut self, _: f32, _: f32) {}
+    fn line_to(&mut self, _: f32, _: f32) {}
+    fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) {}
+    fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) {}
+    fn close(&mut self) {}
+}
+
+fn faces() -> Vec<Face<'static>> {
+    BUNDLED_FONTS
+        .iter()
+        .map(|font| Face::parse(font.data, 0).expect("Bundled fonts must be valid"))
+        .collect()
+}
+
+/// Returns all letters of the preset that contain a character no bundled font can draw.
+fn letters_without_glyph(preset_id: &str) -> Vec<String> {
+    let faces = faces();
+    let preset = find_preset(preset_id).unwrap();
+    preset
+        .letters
+        .iter()
+        .map(|letter| letter.symbol)
+        .filter(|symbol| {
+            !symbol.chars().all(|character| {
+                faces.iter().any(|face| {
+                    face.glyph_index(character)
+                        .and_then(|glyph| face.outline_glyph(glyph, &mut NoOutline))
+                        .is_some()
+                })
+            })
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+fn family_names_match_fonts() {
+    for (font, face) in BUNDLED_FONTS.iter().zip(faces()) {
+        let family = face
+            .names()
+            .into_iter()
+            .filter(|name| name.name_id == ttf_parser::name_id::FAMILY)
+            .find_map(|name| name.to_string());
+        assert_eq!(family.as_deref(), Some(font.family));
+    }
+}
+
+#[test]
+fn phoenician_is_rendered() {
+    assert_eq!(letters_without_glyph("phoenician"), Vec::<String>::new());
+}
+
+#[test]
+fn greek_is_rendered() {
+    assert_eq!(letters_without_glyph("greek"), Vec::<String>::new());
+}
+
+#[test]
+fn runic_is_rendered() {
+    assert_eq!(letters_without_glyph("runic"), Vec::<String>::new());
+}
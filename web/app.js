@@ -0,0 +1,184 @@
+"use strict";
+
+const PAGE_SIZE = 50;
+let dictionaryPage = 0;
+
+async function api(method, path, body) {
+    const response = await fetch(path, {
+        method,
+        headers: { "Content-Type": "application/json" },
+        body: body === undefined ? undefined : JSON.stringify(body),
+    });
+    const result = await response.json();
+    if (!response.ok) {
+        throw new Error(result.error);
+    }
+    return result;
+}
+
+// Runs an action, showing its error at the top of the page if it fails.
+async function attempt(action) {
+    const error = document.getElementById("error");
+    try {
+        await action();
+        error.hidden = true;
+    } catch (e) {
+        error.textContent = e.message;
+        error.hidden = false;
+    }
+}
+
+function cell(text, className) {
+    const td = document.createElement("td");
+    td.textContent = text;
+    if (className) {
+        td.className = className;
+    }
+    return td;
+}
+
+function row(...cells) {
+    const tr = document.createElement("tr");
+    tr.append(...cells);
+    return tr;
+}
+
+function button(label, onClick) {
+    const element = document.createElement("button");
+    element.textContent = label;
+    element.addEventListener("click", () => attempt(onClick));
+    return element;
+}
+
+function buttonCell(label, onClick) {
+    const td = document.createElement("td");
+    td.append(button(label, onClick));
+    return td;
+}
+
+function formatTime(time) {
+    return new Date(time.secs_since_epoch * 1000).toLocaleDateString();
+}
+
+function showTab(name) {
+    for (const tab of document.querySelectorAll("nav button")) {
+        tab.classList.toggle("active", tab.dataset.tab === name);
+        document.getElementById(tab.dataset.tab).hidden = tab.dataset.tab !== name;
+    }
+    const refresh = { dictionary: refreshDictionary, alphabet: refreshAlphabet, "block-list": refreshBlockList }[name];
+    if (refresh) {
+        attempt(refresh);
+    }
+}
+
+async function translate() {
+    const words = await api("POST", "/api/translate", {
+        text: document.getElementById("translate-input").value,
+        dry_run: document.getElementById("translate-dry-run").checked,
+    });
+    document.getElementById("translate-output").textContent = words.map((word) => word.translation).join(" ");
+    document.getElementById("translate-words").replaceChildren(
+        ...words.map((word) => row(cell(word.word), cell(word.translation, "scrambled"), cell(word.new ? "new" : "")))
+    );
+}
+
+async function reverse() {
+    const words = await api("POST", "/api/reverse", { text: document.getElementById("reverse-input").value });
+    document.getElementById("reverse-output").textContent = words
+        .map((word) => word.word ?? `[${word.translation}]`)
+        .join(" ");
+}
+
+async function refreshDictionary() {
+    const result = await api("POST", "/api/dictionary", {
+        search: document.getElementById("dictionary-search").value,
+        direction: document.getElementById("dictionary-direction").value,
+        sort: document.getElementById("dictionary-sort").value,
+        descending: document.getElementById("dictionary-descending").checked,
+        page: dictionaryPage,
+        page_size: PAGE_SIZE,
+    });
+    dictionaryPage = result.page;
+    document.getElementById("dictionary-words").replaceChildren(
+        ...result.entries.map((entry) =>
+            row(cell(entry.word), cell(entry.translation.translation, "scrambled"), cell(formatTime(entry.translation.time_added)))
+        )
+    );
+    document.getElementById("dictionary-page").textContent =
+        `Page ${result.page + 1} of ${result.page_count} (${result.total_matches} words)`;
+    document.getElementById("dictionary-previous").disabled = result.page === 0;
+    document.getElementById("dictionary-next").disabled = result.page + 1 >= result.page_count;
+}
+
+async function refreshAlphabet() {
+    const symbols = await api("GET", "/api/alphabet");
+    document.getElementById("alphabet-letters").replaceChildren(
+        ...symbols.map((symbol) => {
+            const item = document.createElement("li");
+            item.append(symbol, " ", button("Remove", async () => {
+                await api("POST", "/api/alphabet/remove", { symbol });
+                await refreshAlphabet();
+            }));
+            return item;
+        })
+    );
+}
+
+async function refreshBlockList() {
+    const blockList = await api("GET", "/api/block-list");
+    document.getElementById("block-list-words").replaceChildren(
+        ...Object.entries(blockList).map(([word, blocked]) =>
+            row(
+                cell(word, "scrambled"),
+                cell(blocked.reason ?? ""),
+                cell(formatTime(blocked.time_added)),
+                buttonCell("Unblock", async () => {
+                    await api("POST", "/api/block-list/remove", { word });
+                    await refreshBlockList();
+                })
+            )
+        )
+    );
+}
+
+function onSubmit(id, action) {
+    document.getElementById(id).addEventListener("submit", (event) => {
+        event.preventDefault();
+        attempt(action);
+    });
+}
+
+for (const tab of document.querySelectorAll("nav button")) {
+    tab.addEventListener("click", () => showTab(tab.dataset.tab));
+}
+
+onSubmit("translate-form", translate);
+onSubmit("reverse-form", reverse);
+onSubmit("dictionary-form", refreshDictionary);
+onSubmit("alphabet-form", async () => {
+    const input = document.getElementById("alphabet-input");
+    await api("POST", "/api/alphabet", { symbol: input.value });
+    input.value = "";
+    await refreshAlphabet();
+});
+onSubmit("block-list-form", async () => {
+    const word = document.getElementById("block-list-word");
+    const reason = document.getElementById("block-list-reason");
+    await api("POST", "/api/block-list", { word: word.value, reason: reason.value });
+    word.value = "";
+    reason.value = "";
+    await refreshBlockList();
+});
+
+document.getElementById("dictionary-form").addEventListener("input", () => {
+    dictionaryPage = 0;
+    attempt(refreshDictionary);
+});
+document.getElementById("dictionary-previous").addEventListener("click", () => {
+    dictionaryPage -= 1;
+    attempt(refreshDictionary);
+});
+document.getElementById("dictionary-next").addEventListener("click", () => {
+    dictionaryPage += 1;
+    attempt(refreshDictionary);
+});